@@ -0,0 +1,372 @@
+use std::{
+    fmt::{self, Write},
+    ops::{Add, AddAssign, BitAnd, BitOr, Not, Sub, SubAssign},
+    str::FromStr,
+};
+
+use crate::{IPv4Addr, PrefixParseError};
+
+/// 128 bitowy adres IPv6, przechowywany jako dwa u64.
+///
+/// `high` to starsze 64 bity (segmenty 0..4), `low` młodsze (segmenty 4..8).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub struct IPv6Addr {
+    pub high: u64,
+    pub low: u64,
+}
+
+impl IPv6Addr {
+    /// Tworzy adres z dwóch połówek.
+    pub const fn new(high: u64, low: u64) -> Self {
+        Self { high, low }
+    }
+
+    /// Adres jako jedna liczba 128-bitowa.
+    pub const fn to_bits(self) -> u128 {
+        ((self.high as u128) << 64) | self.low as u128
+    }
+
+    /// Adres z liczby 128-bitowej.
+    pub const fn from_bits(bits: u128) -> Self {
+        Self { high: (bits >> 64) as u64, low: bits as u64 }
+    }
+
+    /// Szesnaście bajtów w kolejności sieciowej.
+    pub const fn octets(self) -> [u8; 16] {
+        self.to_bits().to_be_bytes()
+    }
+
+    /// Adres z segmentów, od najstarszego.
+    pub const fn from_segments(segs: [u16; 8]) -> Self {
+        let mut bits = 0u128;
+        let mut i = 0;
+        while i < 8 {
+            bits = (bits << 16) | segs[i] as u128;
+            i += 1;
+        }
+        Self::from_bits(bits)
+    }
+
+    /// `self + n`; `None` przy wyjściu poza `ffff:…:ffff`.
+    pub const fn checked_add(self, n: u128) -> Option<Self> {
+        match self.to_bits().checked_add(n) {
+            Some(bits) => Some(Self::from_bits(bits)),
+            None => None,
+        }
+    }
+
+    /// `self - n`; `None` przy wyjściu poniżej `::`.
+    pub const fn checked_sub(self, n: u128) -> Option<Self> {
+        match self.to_bits().checked_sub(n) {
+            Some(bits) => Some(Self::from_bits(bits)),
+            None => None,
+        }
+    }
+
+    /// `self + n` modulo 2^128.
+    pub const fn wrapping_add(self, n: u128) -> Self {
+        Self::from_bits(self.to_bits().wrapping_add(n))
+    }
+
+    /// `self - n` modulo 2^128.
+    pub const fn wrapping_sub(self, n: u128) -> Self {
+        Self::from_bits(self.to_bits().wrapping_sub(n))
+    }
+
+    /// `self + n`, najwyżej `ffff:…:ffff`.
+    pub const fn saturating_add(self, n: u128) -> Self {
+        Self::from_bits(self.to_bits().saturating_add(n))
+    }
+
+    /// `self - n`, najmniej `::`.
+    pub const fn saturating_sub(self, n: u128) -> Self {
+        Self::from_bits(self.to_bits().saturating_sub(n))
+    }
+
+    /// Liczba kroków między adresami, niezależnie od kolejności.
+    pub const fn distance(self, other: Self) -> u128 {
+        self.to_bits().abs_diff(other.to_bits())
+    }
+
+    /// Następny adres; `None` dla `ffff:…:ffff`.
+    pub const fn next(self) -> Option<Self> {
+        self.checked_add(1)
+    }
+
+    /// Poprzedni adres; `None` dla `::`.
+    pub const fn prev(self) -> Option<Self> {
+        self.checked_sub(1)
+    }
+
+    /// Osiem 16-bitowych segmentów, od najstarszego.
+    pub const fn segments(self) -> [u16; 8] {
+        [
+            (self.high >> 48) as u16,
+            (self.high >> 32) as u16,
+            (self.high >> 16) as u16,
+            self.high as u16,
+            (self.low >> 48) as u16,
+            (self.low >> 32) as u16,
+            (self.low >> 16) as u16,
+            self.low as u16,
+        ]
+    }
+
+    /// Adres IPv4 odwzorowany w IPv6 (`::ffff:a.b.c.d`, RFC 4291 2.5.5.2).
+    pub const fn from_ipv4_mapped(v4: IPv4Addr) -> Self {
+        Self { high: 0, low: 0xffff_0000_0000 | v4.0 as u64 }
+    }
+
+    /// Adres IPv4 zapisany w `::ffff:0:0/96`, jeśli to adres odwzorowany.
+    pub const fn to_ipv4_mapped(self) -> Option<IPv4Addr> {
+        if self.high == 0 && self.low >> 32 == 0xffff {
+            Some(IPv4Addr(self.low as u32))
+        } else {
+            None
+        }
+    }
+
+    /// Ostatnie 32 bity adresu jako IPv4 (np. dla prefiksu NAT64 `64:ff9b::/96`).
+    pub const fn low_ipv4(self) -> IPv4Addr {
+        IPv4Addr(self.low as u32)
+    }
+
+    /// Parsuje tekstową postać adresu (bez `/długość`) zgodnie z RFC 4291 2.2.
+    ///
+    /// Ostatnie 32 bity mogą być zapisane jako adres IPv4, np. `::ffff:192.0.2.1`.
+    pub(crate) fn parse(s: &str) -> Result<Self, PrefixParseError> {
+        let (head, tail, dc_pos) = match s.find("::") {
+            Some(i) => {
+                if let Some(j) = s[i + 1..].find("::") {
+                    return Err(PrefixParseError::TooManyDoubleColons { pos: i + 1 + j });
+                }
+                (&s[..i], Some(&s[i + 2..]), Some(i))
+            }
+            None => (s, None, None),
+        };
+
+        let mut head_segs = Vec::with_capacity(8);
+        let mut tail_segs = Vec::with_capacity(8);
+        let mut index = 0;
+        // część IPv4 dozwolona tylko w ostatniej grupie całego adresu
+        parse_groups(head, 0, &mut index, tail.is_none(), &mut head_segs)?;
+        if let (Some(tail), Some(i)) = (tail, dc_pos) {
+            parse_groups(tail, i + 2, &mut index, true, &mut tail_segs)?;
+        }
+
+        let n = head_segs.len() + tail_segs.len();
+        match dc_pos {
+            None if n < 8 => return Err(PrefixParseError::TooFewSegments),
+            None if n > 8 => return Err(PrefixParseError::TooManySegments),
+            Some(pos) if n == 8 => return Err(PrefixParseError::RedundantDoubleColon { pos }),
+            Some(_) if n > 8 => return Err(PrefixParseError::TooManySegments),
+            _ => {}
+        }
+
+        let mut segs = head_segs;
+        segs.resize(8 - tail_segs.len(), 0);
+        segs.extend(tail_segs);
+
+        let high = ((segs[0] as u64) << 48)
+            | ((segs[1] as u64) << 32)
+            | ((segs[2] as u64) << 16)
+            | (segs[3] as u64);
+        let low  = ((segs[4] as u64) << 48)
+            | ((segs[5] as u64) << 32)
+            | ((segs[6] as u64) << 16)
+            | (segs[7] as u64);
+
+        Ok(IPv6Addr { high, low })
+    }
+}
+
+/// Parsuje grupy oddzielone `:` z fragmentu adresu zaczynającego się na `offset`.
+///
+/// `index` to numer kolejnego segmentu w zapisie (wspólny dla części przed i po `::`).
+fn parse_groups(
+    part: &str,
+    offset: usize,
+    index: &mut usize,
+    ipv4_allowed: bool,
+    segs: &mut Vec<u16>,
+) -> Result<(), PrefixParseError> {
+    if part.is_empty() {
+        return Ok(());
+    }
+    let count = part.split(':').count();
+    let mut pos = offset;
+    for (i, g) in part.split(':').enumerate() {
+        if g.is_empty() {
+            return Err(PrefixParseError::EmptySegment { index: *index, pos });
+        }
+        if g.contains('.') {
+            if !ipv4_allowed || i + 1 != count {
+                return Err(PrefixParseError::MisplacedIpv4 { pos });
+            }
+            let v4: IPv4Addr = g.parse().map_err(|e: PrefixParseError| e.shifted(pos))?;
+            segs.push((v4.0 >> 16) as u16);
+            segs.push(v4.0 as u16);
+        } else {
+            // from_str_radix przyjmuje też `+`, więc cyfry sprawdzamy sami
+            let hex_ok = g.len() <= 4 && g.bytes().all(|b| b.is_ascii_hexdigit());
+            match u16::from_str_radix(g, 16) {
+                Ok(v) if hex_ok => segs.push(v),
+                _ => return Err(PrefixParseError::BadSegment { index: *index, pos }),
+            }
+        }
+        *index += 1;
+        pos += g.len() + 1;
+    }
+    Ok(())
+}
+
+impl FromStr for IPv6Addr {
+    type Err = PrefixParseError;
+
+    /// Sam adres, bez `/długość`, np. `2001:db8::1` albo `::ffff:192.0.2.1`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// Starsze 64 bity dobrze znanego prefiksu NAT64 `64:ff9b::/96`.
+const NAT64_HIGH: u64 = 0x0064_ff9b_0000_0000;
+
+/// Postać kanoniczna wg RFC 5952, np. `2001:db8::1`.
+///
+/// Adresy odwzorowane z IPv4 zapisywane są jako `::ffff:192.0.2.1`, a adresy
+/// z dobrze znanego prefiksu NAT64 (RFC 6052) jako `64:ff9b::192.0.2.1`
+/// (poza samym `64:ff9b::`, żeby prefiks wyglądał jak `64:ff9b::/96`).
+/// `{:#}` daje pełną postać: osiem segmentów po 4 cyfry.
+impl fmt::Display for IPv6Addr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let segs = self.segments();
+        let mut out = String::with_capacity(39);
+
+        if f.alternate() {
+            for (i, seg) in segs.iter().enumerate() {
+                if i > 0 { out.push(':'); }
+                write!(out, "{seg:04x}")?;
+            }
+            return f.pad(&out);
+        }
+        if let Some(v4) = self.to_ipv4_mapped() {
+            return f.pad(&format!("::ffff:{v4}"));
+        }
+        if self.high == NAT64_HIGH && self.low >> 32 == 0 && self.low != 0 {
+            return f.pad(&format!("64:ff9b::{}", self.low_ipv4()));
+        }
+
+        // najdłuższy ciąg zer (min. 2 segmenty), przy remisie pierwszy
+        let (mut best_start, mut best_len) = (0, 0);
+        let mut i = 0;
+        while i < 8 {
+            let start = i;
+            while i < 8 && segs[i] == 0 { i += 1; }
+            if i - start > best_len {
+                (best_start, best_len) = (start, i - start);
+            }
+            i += 1;
+        }
+
+        if best_len < 2 {
+            best_start = 8;
+        }
+        for (i, seg) in segs.iter().enumerate() {
+            if i == best_start {
+                out.push_str("::");
+            } else if best_len >= 2 && i > best_start && i < best_start + best_len {
+                continue;
+            } else {
+                if i > 0 && i != best_start + best_len { out.push(':'); }
+                write!(out, "{seg:x}")?;
+            }
+        }
+        f.pad(&out)
+    }
+}
+
+impl From<u128> for IPv6Addr {
+    fn from(bits: u128) -> Self {
+        Self::from_bits(bits)
+    }
+}
+
+impl From<IPv6Addr> for u128 {
+    fn from(a: IPv6Addr) -> Self {
+        a.to_bits()
+    }
+}
+
+impl From<[u8; 16]> for IPv6Addr {
+    fn from(octets: [u8; 16]) -> Self {
+        Self::from_bits(u128::from_be_bytes(octets))
+    }
+}
+
+impl From<IPv6Addr> for [u8; 16] {
+    fn from(a: IPv6Addr) -> Self {
+        a.octets()
+    }
+}
+
+impl From<[u16; 8]> for IPv6Addr {
+    fn from(segs: [u16; 8]) -> Self {
+        Self::from_segments(segs)
+    }
+}
+
+impl From<IPv6Addr> for [u16; 8] {
+    fn from(a: IPv6Addr) -> Self {
+        a.segments()
+    }
+}
+
+/// Przesunięcie adresu o `n`; panikuje przy przepełnieniu (zob. [`IPv6Addr::checked_add`]).
+impl Add<u128> for IPv6Addr {
+    type Output = Self;
+    fn add(self, n: u128) -> Self {
+        self.checked_add(n).expect("przepełnienie adresu IPv6")
+    }
+}
+
+/// Przesunięcie adresu o `-n`; panikuje poniżej `::` (zob. [`IPv6Addr::checked_sub`]).
+impl Sub<u128> for IPv6Addr {
+    type Output = Self;
+    fn sub(self, n: u128) -> Self {
+        self.checked_sub(n).expect("przepełnienie adresu IPv6")
+    }
+}
+
+impl AddAssign<u128> for IPv6Addr {
+    fn add_assign(&mut self, n: u128) {
+        *self = *self + n;
+    }
+}
+
+impl SubAssign<u128> for IPv6Addr {
+    fn sub_assign(&mut self, n: u128) {
+        *self = *self - n;
+    }
+}
+
+impl BitAnd for IPv6Addr {
+    type Output = Self;
+    fn bitand(self, other: Self) -> Self {
+        Self { high: self.high & other.high, low: self.low & other.low }
+    }
+}
+
+impl BitOr for IPv6Addr {
+    type Output = Self;
+    fn bitor(self, other: Self) -> Self {
+        Self { high: self.high | other.high, low: self.low | other.low }
+    }
+}
+
+impl Not for IPv6Addr {
+    type Output = Self;
+    fn not(self) -> Self {
+        Self { high: !self.high, low: !self.low }
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{IPv4Prefix, IPv6Prefix, IpPrefix, PrefixRelation};
+
+impl IPv6Prefix {
+    /// Najmniejszy zbiór prefiksów pokrywający dokładnie te same adresy.
+    ///
+    /// Prefiksy zawarte w innych są usuwane, a sąsiednie połówki łączone
+    /// w nadprefiks, dopóki się da (np. `2001:db8::/33` + `2001:db8:8000::/33`
+    /// → `2001:db8::/32`). Wynik jest posortowany i ma wyzerowane bity hosta.
+    pub fn aggregate(prefixes: &[Self]) -> Vec<Self> {
+        let mut sorted: Vec<Self> = prefixes.iter().map(Self::normalized).collect();
+        // nadprefiks przed swoimi podprefiksami
+        sorted.sort_unstable_by_key(|p| (p.addr(), p.prefix_len()));
+
+        let mut out: Vec<Self> = Vec::with_capacity(sorted.len());
+        for p in sorted {
+            if out.last().is_some_and(|last| last.overlaps(&p)) {
+                continue;
+            }
+            out.push(p);
+            while out.len() >= 2 {
+                let (a, b) = (out[out.len() - 2], out[out.len() - 1]);
+                if a.relation(&b) != PrefixRelation::Adjacent {
+                    break;
+                }
+                out.truncate(out.len() - 2);
+                out.extend(a.supernet());
+            }
+        }
+        out
+    }
+}
+
+impl IPv4Prefix {
+    /// Najmniejszy zbiór prefiksów pokrywający dokładnie te same adresy,
+    /// jak [`IPv6Prefix::aggregate`].
+    pub fn aggregate(prefixes: &[Self]) -> Vec<Self> {
+        let mapped: Vec<_> = prefixes.iter().map(Self::to_ipv6_mapped).collect();
+        IPv6Prefix::aggregate(&mapped)
+            .iter()
+            .filter_map(IPv6Prefix::to_ipv4_mapped)
+            .collect()
+    }
+}
+
+impl IpPrefix {
+    /// Agregacja osobno dla każdej rodziny; najpierw IPv4, potem IPv6.
+    pub fn aggregate(prefixes: &[Self]) -> Vec<Self> {
+        let (v4, v6) = split_families(prefixes);
+        let mut out: Vec<Self> = IPv4Prefix::aggregate(&v4).into_iter().map(Into::into).collect();
+        out.extend(IPv6Prefix::aggregate(&v6).into_iter().map(IpPrefix::from));
+        out
+    }
+}
+
+/// Rozdziela listę na prefiksy IPv4 i IPv6, zachowując kolejność.
+pub(crate) fn split_families(prefixes: &[IpPrefix]) -> (Vec<IPv4Prefix>, Vec<IPv6Prefix>) {
+    let mut v4 = Vec::new();
+    let mut v6 = Vec::new();
+    for p in prefixes {
+        match *p {
+            IpPrefix::V4(p) => v4.push(p),
+            IpPrefix::V6(p) => v6.push(p),
+        }
+    }
+    (v4, v6)
+}
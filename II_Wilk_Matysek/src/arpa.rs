@@ -0,0 +1,86 @@
+use crate::{IPv6Addr, IPv6Prefix, PrefixParseError};
+
+/// Domena nazw odwrotnych IPv6 (RFC 3596).
+const SUFFIX: &str = "ip6.arpa";
+
+/// Nazwa z `nibbles` najstarszych cyfr szesnastkowych `bits`, od najmłodszej z nich.
+fn nibble_name(bits: u128, nibbles: u32) -> String {
+    let mut name = String::with_capacity(2 * nibbles as usize + SUFFIX.len());
+    for i in (0..nibbles).rev() {
+        let nibble = (bits >> (124 - 4 * i)) as u32 & 0xf;
+        name.push(char::from_digit(nibble, 16).expect("nibble < 16"));
+        name.push('.');
+    }
+    name + SUFFIX
+}
+
+/// Cyfry nazwy `….ip6.arpa` jako najstarsze bity adresu oraz ich liczba.
+fn parse_nibbles(name: &str) -> Result<(u128, u32), PrefixParseError> {
+    use PrefixParseError::*;
+    let name = name.strip_suffix('.').unwrap_or(name);
+    let split = name.len().checked_sub(SUFFIX.len()).ok_or(NotIp6Arpa)?;
+    // sufiks jest ASCII, więc po udanym porównaniu `split` leży na granicy znaku
+    if !name.as_bytes()[split..].eq_ignore_ascii_case(SUFFIX.as_bytes()) {
+        return Err(NotIp6Arpa);
+    }
+    let labels = match &name[..split] {
+        "" => return Ok((0, 0)),
+        labels => labels.strip_suffix('.').ok_or(NotIp6Arpa)?,
+    };
+    let (mut bits, mut count, mut pos) = (0u128, 0u32, 0usize);
+    for (index, label) in labels.split('.').enumerate() {
+        if count == 32 {
+            return Err(TooManyNibbles { pos });
+        }
+        let digit = match label.as_bytes() {
+            &[b] => char::from(b).to_digit(16),
+            _ => None,
+        };
+        let digit = digit.ok_or(BadNibble { index, pos })?;
+        // pierwsza etykieta to najmłodsza cyfra, ostatnia trafia na sam początek adresu
+        bits = (bits >> 4) | (u128::from(digit) << 124);
+        count += 1;
+        pos += label.len() + 1;
+    }
+    Ok((bits, count))
+}
+
+impl IPv6Addr {
+    /// Nazwa rekordu PTR: 32 cyfry szesnastkowe od najmłodszej, np.
+    /// `1.0.0.0.….8.b.d.0.1.0.0.2.ip6.arpa` dla `2001:db8::1` (bez kropki na końcu).
+    pub fn to_ptr_name(self) -> String {
+        nibble_name(self.to_bits(), 32)
+    }
+
+    /// Adres z nazwy PTR, odwrotność [`to_ptr_name`](Self::to_ptr_name).
+    ///
+    /// Wielkość liter i kropka na końcu nie mają znaczenia; nazwa musi mieć
+    /// wszystkie 32 cyfry.
+    pub fn from_ptr_name(name: &str) -> Result<Self, PrefixParseError> {
+        match parse_nibbles(name)? {
+            (bits, 32) => Ok(Self::from_bits(bits)),
+            _ => Err(PrefixParseError::TooFewNibbles),
+        }
+    }
+}
+
+impl IPv6Prefix {
+    /// Strefy `ip6.arpa` pokrywające dokładnie ten prefiks.
+    ///
+    /// Strefa obejmuje całe cyfry szesnastkowe, więc prefiks o długości
+    /// niepodzielnej przez 4 daje 2, 4 albo 8 stref (podsieci o długości
+    /// zaokrąglonej w górę), np. `2001:db8::/31` daje `8.b.d.0.1.0.0.2.ip6.arpa`
+    /// i `9.b.d.0.1.0.0.2.ip6.arpa`.
+    pub fn to_ip6_arpa(&self) -> Vec<String> {
+        let len = self.prefix_len().div_ceil(4) * 4;
+        self.subnets(len).map(|p| nibble_name(p.network().to_bits(), u32::from(len / 4))).collect()
+    }
+
+    /// Prefiks ze strefy `ip6.arpa`; długość to cztery bity na każdą cyfrę.
+    ///
+    /// Jak w [`IPv6Addr::from_ptr_name`], ale liczba cyfr może wynosić od 0 do 32.
+    pub fn from_ip6_arpa(name: &str) -> Result<Self, PrefixParseError> {
+        let (bits, nibbles) = parse_nibbles(name)?;
+        Ok(Self::new(IPv6Addr::from_bits(bits), 4 * nibbles as u8).expect("nibbles <= 32"))
+    }
+}
@@ -0,0 +1,268 @@
+use std::fmt;
+
+use crate::{IPv6Addr, IPv6Prefix, Lang, Localize};
+
+/// Zasięg adresu multicast (RFC 7346), z czwartego nibble'a adresu.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum MulticastScope {
+    InterfaceLocal,
+    LinkLocal,
+    RealmLocal,
+    AdminLocal,
+    SiteLocal,
+    OrganizationLocal,
+    Global,
+    /// Wartość zarezerwowana lub nieprzydzielona (0, 6, 7, 9–d, f).
+    Other(u8),
+}
+
+impl MulticastScope {
+    fn from_nibble(n: u8) -> Self {
+        match n {
+            0x1 => MulticastScope::InterfaceLocal,
+            0x2 => MulticastScope::LinkLocal,
+            0x3 => MulticastScope::RealmLocal,
+            0x4 => MulticastScope::AdminLocal,
+            0x5 => MulticastScope::SiteLocal,
+            0x8 => MulticastScope::OrganizationLocal,
+            0xe => MulticastScope::Global,
+            n => MulticastScope::Other(n),
+        }
+    }
+
+    /// Stała nazwa do wyjścia maszynowego.
+    pub fn name(&self) -> &'static str {
+        match self {
+            MulticastScope::InterfaceLocal => "interface-local",
+            MulticastScope::LinkLocal => "link-local",
+            MulticastScope::RealmLocal => "realm-local",
+            MulticastScope::AdminLocal => "admin-local",
+            MulticastScope::SiteLocal => "site-local",
+            MulticastScope::OrganizationLocal => "organization-local",
+            MulticastScope::Global => "global",
+            MulticastScope::Other(_) => "reserved",
+        }
+    }
+}
+
+/// Rodzaj adresu wg RFC 4291 i rejestru IANA IPv6 Special-Purpose Address Registry.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum AddrClass {
+    /// `::/128`
+    Unspecified,
+    /// `::1/128`
+    Loopback,
+    /// `::ffff:0:0/96`
+    Ipv4Mapped,
+    /// `64:ff9b::/96`, dobrze znany prefiks NAT64 (RFC 6052)
+    Nat64,
+    /// `64:ff9b:1::/48`, lokalne translacje IPv4/IPv6 (RFC 8215)
+    LocalNat64,
+    /// `100::/64` (RFC 6666)
+    DiscardOnly,
+    /// `2001::/23` poza bardziej szczegółowymi wpisami
+    IetfProtocol,
+    /// `2001::/32`
+    Teredo,
+    /// `2001:2::/48` (RFC 5180)
+    Benchmarking,
+    /// `2001:3::/32` (RFC 7450)
+    Amt,
+    /// `2001:4:112::/48` i `2620:4f:8000::/48` (RFC 7534, 7535)
+    As112,
+    /// `2001:10::/28`, wycofany (RFC 4843)
+    Orchid,
+    /// `2001:20::/28` (RFC 7343)
+    OrchidV2,
+    /// `2001:30::/28` (RFC 9374)
+    DroneRemoteId,
+    /// `2001:db8::/32` i `3fff::/20` (RFC 3849, 9637)
+    Documentation,
+    /// `2002::/16` (RFC 3056)
+    SixToFour,
+    /// `5f00::/16` (RFC 9602)
+    Srv6Sid,
+    /// `fc00::/7` (RFC 4193)
+    UniqueLocal,
+    /// `fe80::/10`
+    LinkLocal,
+    /// `fec0::/10`, wycofany (RFC 3879)
+    SiteLocal,
+    /// `ff00::/8`
+    Multicast(MulticastScope),
+    /// Reszta `2000::/3`
+    GlobalUnicast,
+    /// Przestrzeń nieprzydzielona przez IANA
+    Reserved,
+}
+
+/// Wpisy rejestru: (adres sieci, długość, rodzaj). Zasięg multicastu uzupełnia `with_scope`.
+const REGISTRY: &[(u128, u8, AddrClass)] = &[
+    (0, 0, AddrClass::Reserved),
+    (0, 128, AddrClass::Unspecified),
+    (1, 128, AddrClass::Loopback),
+    (0xffff_0000_0000, 96, AddrClass::Ipv4Mapped),
+    (0x0064_ff9b << 96, 96, AddrClass::Nat64),
+    (0x0064_ff9b_0001 << 80, 48, AddrClass::LocalNat64),
+    (0x0100 << 112, 64, AddrClass::DiscardOnly),
+    (0x2000 << 112, 3, AddrClass::GlobalUnicast),
+    (0x2001 << 112, 23, AddrClass::IetfProtocol),
+    (0x2001 << 112, 32, AddrClass::Teredo),
+    (0x2001_0002 << 96, 48, AddrClass::Benchmarking),
+    (0x2001_0003 << 96, 32, AddrClass::Amt),
+    (0x2001_0004_0112 << 80, 48, AddrClass::As112),
+    (0x2001_0010 << 96, 28, AddrClass::Orchid),
+    (0x2001_0020 << 96, 28, AddrClass::OrchidV2),
+    (0x2001_0030 << 96, 28, AddrClass::DroneRemoteId),
+    (0x2001_0db8 << 96, 32, AddrClass::Documentation),
+    (0x2002 << 112, 16, AddrClass::SixToFour),
+    (0x2620_004f_8000 << 80, 48, AddrClass::As112),
+    (0x3fff << 112, 20, AddrClass::Documentation),
+    (0x5f00 << 112, 16, AddrClass::Srv6Sid),
+    (0xfc00 << 112, 7, AddrClass::UniqueLocal),
+    (0xfe80 << 112, 10, AddrClass::LinkLocal),
+    (0xfec0 << 112, 10, AddrClass::SiteLocal),
+    (0xff00 << 112, 8, AddrClass::Multicast(MulticastScope::Other(0))),
+];
+
+fn registry() -> impl Iterator<Item = (IPv6Prefix, AddrClass)> {
+    REGISTRY.iter().map(|&(bits, len, class)| {
+        (IPv6Prefix::new(IPv6Addr::from_bits(bits), len).expect("len <= 128"), class)
+    })
+}
+
+/// Dla multicastu dopisuje zasięg z adresu.
+fn with_scope(class: AddrClass, addr: IPv6Addr) -> AddrClass {
+    match class {
+        AddrClass::Multicast(_) => {
+            AddrClass::Multicast(MulticastScope::from_nibble((addr.high >> 48) as u8 & 0xf))
+        }
+        c => c,
+    }
+}
+
+impl AddrClass {
+    /// Czy to przestrzeń specjalnego przeznaczenia (wszystko poza zwykłym global unicast)?
+    pub fn is_special(&self) -> bool {
+        *self != AddrClass::GlobalUnicast
+    }
+
+    /// Stała, angielska nazwa do wyjścia maszynowego.
+    pub fn name(&self) -> &'static str {
+        match self {
+            AddrClass::Unspecified => "unspecified",
+            AddrClass::Loopback => "loopback",
+            AddrClass::Ipv4Mapped => "ipv4-mapped",
+            AddrClass::Nat64 => "nat64",
+            AddrClass::LocalNat64 => "local-nat64",
+            AddrClass::DiscardOnly => "discard-only",
+            AddrClass::IetfProtocol => "ietf-protocol",
+            AddrClass::Teredo => "teredo",
+            AddrClass::Benchmarking => "benchmarking",
+            AddrClass::Amt => "amt",
+            AddrClass::As112 => "as112",
+            AddrClass::Orchid => "orchid",
+            AddrClass::OrchidV2 => "orchidv2",
+            AddrClass::DroneRemoteId => "drone-remote-id",
+            AddrClass::Documentation => "documentation",
+            AddrClass::SixToFour => "6to4",
+            AddrClass::Srv6Sid => "srv6-sid",
+            AddrClass::UniqueLocal => "unique-local",
+            AddrClass::LinkLocal => "link-local",
+            AddrClass::SiteLocal => "site-local",
+            AddrClass::Multicast(_) => "multicast",
+            AddrClass::GlobalUnicast => "global-unicast",
+            AddrClass::Reserved => "reserved",
+        }
+    }
+}
+
+impl fmt::Display for AddrClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_in(Lang::En, f)
+    }
+}
+
+impl Localize for AddrClass {
+    fn fmt_in(&self, lang: Lang, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use AddrClass::*;
+        if let Multicast(scope) = self {
+            return match lang {
+                Lang::En => write!(f, "multicast, {} scope", scope.name()),
+                Lang::Pl => write!(f, "multicast, zasięg {}", scope.name()),
+            };
+        }
+        f.write_str(match (lang, self) {
+            (_, Teredo) => "Teredo",
+            (_, Amt) => "AMT",
+            (_, As112) => "AS112",
+            (_, OrchidV2) => "ORCHIDv2",
+            (_, DroneRemoteId) => "Drone Remote ID",
+            (_, SixToFour) => "6to4",
+            (_, Srv6Sid) => "SRv6 SID",
+            (Lang::En, Unspecified) => "unspecified address",
+            (Lang::En, Loopback) => "loopback",
+            (Lang::En, Ipv4Mapped) => "IPv4-mapped IPv6",
+            (Lang::En, Nat64) => "NAT64, well-known prefix",
+            (Lang::En, LocalNat64) => "NAT64, local-use",
+            (Lang::En, DiscardOnly) => "discard-only",
+            (Lang::En, IetfProtocol) => "IETF protocol assignments",
+            (Lang::En, Benchmarking) => "benchmarking",
+            (Lang::En, Orchid) => "ORCHID (deprecated)",
+            (Lang::En, Documentation) => "documentation",
+            (Lang::En, UniqueLocal) => "unique local (ULA)",
+            (Lang::En, LinkLocal) => "link-local",
+            (Lang::En, SiteLocal) => "site-local (deprecated)",
+            (Lang::En, GlobalUnicast) => "global unicast",
+            (Lang::En, Reserved) => "reserved",
+            (Lang::Pl, Unspecified) => "adres nieokreślony",
+            (Lang::Pl, Loopback) => "pętla zwrotna (loopback)",
+            (Lang::Pl, Ipv4Mapped) => "IPv4 odwzorowany w IPv6",
+            (Lang::Pl, Nat64) => "NAT64, prefiks dobrze znany",
+            (Lang::Pl, LocalNat64) => "NAT64, lokalny",
+            (Lang::Pl, DiscardOnly) => "tylko do odrzucania (discard-only)",
+            (Lang::Pl, IetfProtocol) => "przydziały protokołów IETF",
+            (Lang::Pl, Benchmarking) => "testy wydajności (benchmarking)",
+            (Lang::Pl, Orchid) => "ORCHID (wycofany)",
+            (Lang::Pl, Documentation) => "dokumentacja",
+            (Lang::Pl, UniqueLocal) => "unikalny lokalny (ULA)",
+            (Lang::Pl, LinkLocal) => "lokalny łącza (link-local)",
+            (Lang::Pl, SiteLocal) => "lokalny ośrodka (site-local, wycofany)",
+            (Lang::Pl, GlobalUnicast) => "globalny unicast",
+            (Lang::Pl, Reserved) => "zarezerwowany",
+            (_, Multicast(_)) => unreachable!("obsłużone wyżej"),
+        })
+    }
+}
+
+impl IPv6Addr {
+    /// Rodzaj adresu: najbardziej szczegółowy pasujący wpis rejestru IANA.
+    pub fn classify(self) -> AddrClass {
+        let class = registry()
+            .filter(|(p, _)| p.contains_addr(self))
+            .max_by_key(|(p, _)| p.prefix_len())
+            .map_or(AddrClass::Reserved, |(_, c)| c);
+        with_scope(class, self)
+    }
+}
+
+impl IPv6Prefix {
+    /// Rodzaj wszystkich adresów prefiksu albo `None`, gdy prefiks obejmuje
+    /// przestrzeń kilku rodzajów (np. `::/0` albo `2001::/16`).
+    pub fn classify(&self) -> Option<AddrClass> {
+        let (class_prefix, class) = registry()
+            .filter(|(p, _)| {
+                p.prefix_len() <= self.prefix_len() && p.contains_addr(self.network())
+            })
+            .max_by_key(|(p, _)| p.prefix_len())?;
+        // bardziej szczegółowy wpis w środku prefiksu oznacza mieszankę
+        let mixed = registry().any(|(p, c)| {
+            p.prefix_len() > class_prefix.prefix_len()
+                && c != class
+                && self.contains_addr(p.network())
+        });
+        // zasięg multicastu jest ustalony dopiero od /16
+        let unscoped = matches!(class, AddrClass::Multicast(_)) && self.prefix_len() < 16;
+        (!mixed && !unscoped).then(|| with_scope(class, self.network()))
+    }
+}
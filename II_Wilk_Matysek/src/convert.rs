@@ -0,0 +1,120 @@
+use std::{
+    error::Error,
+    fmt,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+};
+
+use crate::{Family, IPv4Addr, IPv6Addr, Lang, Localize};
+
+/// Konwersja adresu z innej rodziny niż typ docelowy, np. IPv4 do [`IPv6Addr`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct AddrFamilyMismatch {
+    /// Rodzina typu docelowego.
+    pub expected: Family,
+}
+
+impl fmt::Display for AddrFamilyMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_in(Lang::En, f)
+    }
+}
+
+impl Localize for AddrFamilyMismatch {
+    fn fmt_in(&self, lang: Lang, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (expected, given) = match self.expected {
+            Family::V4 => ("IPv4", "IPv6"),
+            Family::V6 => ("IPv6", "IPv4"),
+        };
+        match lang {
+            Lang::En => write!(f, "Expected an {expected} address, got {given}"),
+            Lang::Pl => write!(f, "Oczekiwano adresu {expected}, podano {given}"),
+        }
+    }
+}
+
+impl Error for AddrFamilyMismatch {}
+
+impl From<Ipv6Addr> for IPv6Addr {
+    fn from(a: Ipv6Addr) -> Self {
+        Self::from_bits(a.to_bits())
+    }
+}
+
+impl From<IPv6Addr> for Ipv6Addr {
+    fn from(a: IPv6Addr) -> Self {
+        Ipv6Addr::from_bits(a.to_bits())
+    }
+}
+
+impl From<Ipv4Addr> for IPv4Addr {
+    fn from(a: Ipv4Addr) -> Self {
+        IPv4Addr(a.to_bits())
+    }
+}
+
+impl From<IPv4Addr> for Ipv4Addr {
+    fn from(a: IPv4Addr) -> Self {
+        Ipv4Addr::from_bits(a.0)
+    }
+}
+
+impl From<IPv6Addr> for IpAddr {
+    fn from(a: IPv6Addr) -> Self {
+        IpAddr::V6(a.into())
+    }
+}
+
+impl From<IPv4Addr> for IpAddr {
+    fn from(a: IPv4Addr) -> Self {
+        IpAddr::V4(a.into())
+    }
+}
+
+/// Tylko `IpAddr::V6`; adres IPv4 nie jest odwzorowywany w `::ffff:0:0/96`.
+impl TryFrom<IpAddr> for IPv6Addr {
+    type Error = AddrFamilyMismatch;
+    fn try_from(a: IpAddr) -> Result<Self, AddrFamilyMismatch> {
+        match a {
+            IpAddr::V6(a) => Ok(a.into()),
+            IpAddr::V4(_) => Err(AddrFamilyMismatch { expected: Family::V6 }),
+        }
+    }
+}
+
+impl TryFrom<IpAddr> for IPv4Addr {
+    type Error = AddrFamilyMismatch;
+    fn try_from(a: IpAddr) -> Result<Self, AddrFamilyMismatch> {
+        match a {
+            IpAddr::V4(a) => Ok(a.into()),
+            IpAddr::V6(_) => Err(AddrFamilyMismatch { expected: Family::V4 }),
+        }
+    }
+}
+
+/// Adres z gniazda, bez portu, flowinfo i scope id.
+impl From<SocketAddrV6> for IPv6Addr {
+    fn from(sa: SocketAddrV6) -> Self {
+        (*sa.ip()).into()
+    }
+}
+
+/// Adres z gniazda, bez portu.
+impl From<SocketAddrV4> for IPv4Addr {
+    fn from(sa: SocketAddrV4) -> Self {
+        (*sa.ip()).into()
+    }
+}
+
+impl TryFrom<SocketAddr> for IPv6Addr {
+    type Error = AddrFamilyMismatch;
+    fn try_from(sa: SocketAddr) -> Result<Self, AddrFamilyMismatch> {
+        sa.ip().try_into()
+    }
+}
+
+impl TryFrom<SocketAddr> for IPv4Addr {
+    type Error = AddrFamilyMismatch;
+    fn try_from(sa: SocketAddr) -> Result<Self, AddrFamilyMismatch> {
+        sa.ip().try_into()
+    }
+}
@@ -0,0 +1,213 @@
+use std::{error::Error, fmt};
+
+use crate::{Lang, Localize};
+
+/// Błąd parsowania adresu lub prefiksu.
+///
+/// `pos` to przesunięcie w bajtach od początku parsowanego napisu,
+/// `index` to numer segmentu (oktetu) w kolejności zapisu, liczony od 0.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum PrefixParseError {
+    /// Brak `/` oddzielającego adres od długości maski.
+    MissingSlash,
+    /// Długość maski nie jest liczbą dziesiętną.
+    BadLength { pos: usize },
+    /// Długość maski większa niż `max` (32 lub 128).
+    LengthTooLarge { pos: usize, max: u8 },
+    /// Więcej niż jedno `::` (w tym `:::`).
+    TooManyDoubleColons { pos: usize },
+    /// Pusty segment, np. pojedynczy `:` na początku lub końcu adresu.
+    EmptySegment { index: usize, pos: usize },
+    /// Segment nie jest liczbą szesnastkową z 1 do 4 cyfr.
+    BadSegment { index: usize, pos: usize },
+    /// Więcej niż osiem segmentów.
+    TooManySegments,
+    /// Mniej niż osiem segmentów bez `::`.
+    TooFewSegments,
+    /// `::` w adresie, który ma już komplet ośmiu segmentów.
+    RedundantDoubleColon { pos: usize },
+    /// Część IPv4 nie na końcu adresu IPv6.
+    MisplacedIpv4 { pos: usize },
+    /// Adres IPv4 nie ma dokładnie czterech oktetów.
+    BadOctetCount { pos: usize },
+    /// Oktet nie jest liczbą 0..=255 bez zer wiodących.
+    BadOctet { index: usize, pos: usize },
+    /// Adres ma ustawione bity poza maską (tryb [`HostBitsPolicy::Strict`]).
+    ///
+    /// [`HostBitsPolicy::Strict`]: crate::HostBitsPolicy::Strict
+    HostBitsSet,
+    /// Brak `-` oddzielającego początek i koniec zakresu.
+    MissingDash,
+    /// Zakres `a-b`, w którym `a` > `b`; `pos` wskazuje `b`.
+    ReversedRange { pos: usize },
+    /// Nazwa nie kończy się na `ip6.arpa`.
+    NotIp6Arpa,
+    /// Etykieta przed `ip6.arpa` nie jest pojedynczą cyfrą szesnastkową.
+    BadNibble { index: usize, pos: usize },
+    /// Więcej niż 32 cyfry przed `ip6.arpa`; `pos` wskazuje pierwszą nadmiarową.
+    TooManyNibbles { pos: usize },
+    /// Nazwa PTR bez kompletu 32 cyfr.
+    TooFewNibbles,
+}
+
+impl PrefixParseError {
+    /// Pozycja błędu w napisie, jeśli da się ją wskazać.
+    pub fn position(&self) -> Option<usize> {
+        use PrefixParseError::*;
+        match *self {
+            MissingSlash | MissingDash | TooManySegments | TooFewSegments | HostBitsSet
+            | NotIp6Arpa | TooFewNibbles => None,
+            BadLength { pos }
+            | LengthTooLarge { pos, .. }
+            | TooManyDoubleColons { pos }
+            | EmptySegment { pos, .. }
+            | BadSegment { pos, .. }
+            | RedundantDoubleColon { pos }
+            | MisplacedIpv4 { pos }
+            | BadOctetCount { pos }
+            | BadOctet { pos, .. }
+            | ReversedRange { pos }
+            | BadNibble { pos, .. }
+            | TooManyNibbles { pos } => Some(pos),
+        }
+    }
+
+    /// Przesuwa pozycję o `offset` (gdy parsowano fragment większego napisu).
+    pub(crate) fn shifted(self, offset: usize) -> Self {
+        use PrefixParseError::*;
+        match self {
+            BadLength { pos } => BadLength { pos: pos + offset },
+            LengthTooLarge { pos, max } => LengthTooLarge { pos: pos + offset, max },
+            TooManyDoubleColons { pos } => TooManyDoubleColons { pos: pos + offset },
+            EmptySegment { index, pos } => EmptySegment { index, pos: pos + offset },
+            BadSegment { index, pos } => BadSegment { index, pos: pos + offset },
+            RedundantDoubleColon { pos } => RedundantDoubleColon { pos: pos + offset },
+            MisplacedIpv4 { pos } => MisplacedIpv4 { pos: pos + offset },
+            BadOctetCount { pos } => BadOctetCount { pos: pos + offset },
+            BadOctet { index, pos } => BadOctet { index, pos: pos + offset },
+            ReversedRange { pos } => ReversedRange { pos: pos + offset },
+            BadNibble { index, pos } => BadNibble { index, pos: pos + offset },
+            TooManyNibbles { pos } => TooManyNibbles { pos: pos + offset },
+            // warianty bez pozycji
+            e => e,
+        }
+    }
+}
+
+impl fmt::Display for PrefixParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_in(Lang::En, f)
+    }
+}
+
+impl Localize for PrefixParseError {
+    fn fmt_in(&self, lang: Lang, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match lang {
+            Lang::En => self.fmt_en(f),
+            Lang::Pl => self.fmt_pl(f),
+        }
+    }
+}
+
+impl PrefixParseError {
+    fn fmt_en(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use PrefixParseError::*;
+        match self {
+            MissingSlash => write!(f, "Missing ‘/’ in prefix"),
+            BadLength { pos } => write!(f, "Invalid prefix length (position {pos})"),
+            LengthTooLarge { pos, max } => {
+                write!(f, "Prefix length > {max} (position {pos})")
+            }
+            TooManyDoubleColons { pos } => write!(f, "Too many ‘::’ (position {pos})"),
+            EmptySegment { index, pos } => {
+                write!(f, "Empty segment #{index} (position {pos})")
+            }
+            BadSegment { index, pos } => {
+                write!(f, "Invalid IPv6 segment #{index} (position {pos})")
+            }
+            TooManySegments => write!(f, "Too many IPv6 segments"),
+            TooFewSegments => write!(f, "Too few IPv6 segments"),
+            RedundantDoubleColon { pos } => {
+                write!(f, "Redundant ‘::’ with eight segments (position {pos})")
+            }
+            MisplacedIpv4 { pos } => {
+                write!(f, "IPv4 part must end the address (position {pos})")
+            }
+            BadOctetCount { pos } => {
+                write!(f, "IPv4 address must have 4 octets (position {pos})")
+            }
+            BadOctet { index, pos } => {
+                write!(f, "Invalid IPv4 octet #{index} (position {pos})")
+            }
+            HostBitsSet => write!(f, "Address has bits set outside the mask"),
+            MissingDash => write!(f, "Missing ‘-’ in range"),
+            ReversedRange { pos } => {
+                write!(f, "Range ends before it starts (position {pos})")
+            }
+            NotIp6Arpa => write!(f, "Name does not end in ‘ip6.arpa’"),
+            BadNibble { index, pos } => {
+                write!(f, "Label #{index} is not a single hex digit (position {pos})")
+            }
+            TooManyNibbles { pos } => {
+                write!(f, "More than 32 hex digits before ‘ip6.arpa’ (position {pos})")
+            }
+            TooFewNibbles => write!(f, "PTR name must have 32 hex digits"),
+        }
+    }
+
+    fn fmt_pl(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use PrefixParseError::*;
+        match self {
+            MissingSlash => write!(f, "Brak ‘/’ w prefiksie"),
+            BadLength { pos } => write!(f, "Niepoprawna długość maski (pozycja {pos})"),
+            LengthTooLarge { pos, max } => write!(f, "Maska > {max} (pozycja {pos})"),
+            TooManyDoubleColons { pos } => write!(f, "Za dużo ‘::’ (pozycja {pos})"),
+            EmptySegment { index, pos } => {
+                write!(f, "Pusty segment nr {index} (pozycja {pos})")
+            }
+            BadSegment { index, pos } => {
+                write!(f, "Błędny segment IPv6 nr {index} (pozycja {pos})")
+            }
+            TooManySegments => write!(f, "Zbyt wiele segmentów IPv6"),
+            TooFewSegments => write!(f, "Zbyt mało segmentów IPv6"),
+            RedundantDoubleColon { pos } => {
+                write!(f, "Zbędne ‘::’ przy ośmiu segmentach (pozycja {pos})")
+            }
+            MisplacedIpv4 { pos } => {
+                write!(f, "Część IPv4 musi być na końcu adresu (pozycja {pos})")
+            }
+            BadOctetCount { pos } => {
+                write!(f, "Adres IPv4 musi mieć 4 oktety (pozycja {pos})")
+            }
+            BadOctet { index, pos } => {
+                write!(f, "Błędny oktet IPv4 nr {index} (pozycja {pos})")
+            }
+            HostBitsSet => write!(f, "Adres ma ustawione bity poza maską"),
+            MissingDash => write!(f, "Brak ‘-’ w zakresie"),
+            ReversedRange { pos } => {
+                write!(f, "Koniec zakresu przed jego początkiem (pozycja {pos})")
+            }
+            NotIp6Arpa => write!(f, "Nazwa nie kończy się na ‘ip6.arpa’"),
+            BadNibble { index, pos } => {
+                write!(f, "Etykieta nr {index} nie jest cyfrą szesnastkową (pozycja {pos})")
+            }
+            TooManyNibbles { pos } => {
+                write!(f, "Więcej niż 32 cyfry przed ‘ip6.arpa’ (pozycja {pos})")
+            }
+            TooFewNibbles => write!(f, "Nazwa PTR musi mieć 32 cyfry szesnastkowe"),
+        }
+    }
+}
+
+impl Error for PrefixParseError {}
+
+/// Parsuje długość maski zapisaną po `/`; `pos` to pozycja pierwszej cyfry.
+pub(crate) fn parse_len(s: &str, pos: usize, max: u8) -> Result<u8, PrefixParseError> {
+    if s.is_empty() || s.len() > 3 || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(PrefixParseError::BadLength { pos });
+    }
+    match s.parse::<u8>() {
+        Ok(len) if len <= max => Ok(len),
+        _ => Err(PrefixParseError::LengthTooLarge { pos, max }),
+    }
+}
@@ -0,0 +1,67 @@
+use crate::{IPv4Prefix, IPv6Prefix, IpPrefix, aggregate::split_families};
+
+impl IPv6Prefix {
+    /// Adresy z `self` bez adresów z `other`, jako najmniejsza lista prefiksów.
+    ///
+    /// Gdy `other` leży wewnątrz `self`, wynik ma `other.len - self.len`
+    /// prefiksów: na każdym poziomie połówkę, która nie zawiera `other`.
+    pub fn difference(&self, other: &Self) -> Vec<Self> {
+        let this = self.normalized();
+        if !this.overlaps(other) {
+            return vec![this];
+        }
+        if other.prefix_len() <= this.prefix_len() {
+            // other pokrywa cały self
+            return Vec::new();
+        }
+
+        let mut out = Vec::with_capacity((other.prefix_len() - this.prefix_len()) as usize);
+        // other.len > this.len >= 0, więc wszystkie te nadprefiksy mają rodzeństwo
+        for len in this.prefix_len() + 1..=other.prefix_len() {
+            out.extend(other.supernet_of_len(len).and_then(|p| p.sibling()));
+        }
+        out.sort_unstable_by_key(|p| p.addr());
+        out
+    }
+
+    /// Adresy z `from` bez adresów z `exclude`, jako najmniejsza lista prefiksów.
+    pub fn difference_set(from: &[Self], exclude: &[Self]) -> Vec<Self> {
+        let mut rest = Self::aggregate(from);
+        for ex in Self::aggregate(exclude) {
+            rest = rest.iter().flat_map(|p| p.difference(&ex)).collect();
+        }
+        Self::aggregate(&rest)
+    }
+}
+
+impl IPv4Prefix {
+    /// Adresy z `self` bez adresów z `other`, jak [`IPv6Prefix::difference`].
+    pub fn difference(&self, other: &Self) -> Vec<Self> {
+        self.to_ipv6_mapped()
+            .difference(&other.to_ipv6_mapped())
+            .iter()
+            .filter_map(IPv6Prefix::to_ipv4_mapped)
+            .collect()
+    }
+
+    /// Adresy z `from` bez adresów z `exclude`, jak [`IPv6Prefix::difference_set`].
+    pub fn difference_set(from: &[Self], exclude: &[Self]) -> Vec<Self> {
+        let map = |ps: &[Self]| ps.iter().map(Self::to_ipv6_mapped).collect::<Vec<_>>();
+        IPv6Prefix::difference_set(&map(from), &map(exclude))
+            .iter()
+            .filter_map(IPv6Prefix::to_ipv4_mapped)
+            .collect()
+    }
+}
+
+impl IpPrefix {
+    /// Różnica zbiorów osobno dla każdej rodziny; najpierw IPv4, potem IPv6.
+    pub fn difference_set(from: &[Self], exclude: &[Self]) -> Vec<Self> {
+        let (from4, from6) = split_families(from);
+        let (ex4, ex6) = split_families(exclude);
+        let mut out: Vec<Self> =
+            IPv4Prefix::difference_set(&from4, &ex4).into_iter().map(Into::into).collect();
+        out.extend(IPv6Prefix::difference_set(&from6, &ex6).into_iter().map(IpPrefix::from));
+        out
+    }
+}
@@ -0,0 +1,155 @@
+use std::{error::Error, fmt, net::IpAddr, str::FromStr};
+
+use crate::{
+    HostBitsPolicy, HostBitsWarning, IPv4Prefix, IPv6Prefix, Lang, Localize, PrefixParseError,
+    PrefixRelation,
+};
+
+/// Rodzina adresów.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Family {
+    V4,
+    V6,
+}
+
+/// Prefiks IPv4 albo IPv6.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum IpPrefix {
+    V4(IPv4Prefix),
+    V6(IPv6Prefix),
+}
+
+/// Próba porównania prefiksów z różnych rodzin adresów.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct FamilyMismatch;
+
+impl fmt::Display for FamilyMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_in(Lang::En, f)
+    }
+}
+
+impl Localize for FamilyMismatch {
+    fn fmt_in(&self, lang: Lang, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match lang {
+            Lang::En => "Prefixes from different address families (IPv4 and IPv6)",
+            Lang::Pl => "Prefiksy z różnych rodzin adresów (IPv4 i IPv6)",
+        })
+    }
+}
+
+impl Error for FamilyMismatch {}
+
+impl fmt::Display for IpPrefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IpPrefix::V4(p) => fmt::Display::fmt(p, f),
+            IpPrefix::V6(p) => fmt::Display::fmt(p, f),
+        }
+    }
+}
+
+impl FromStr for IpPrefix {
+    type Err = PrefixParseError;
+
+    /// Rodzina rozpoznawana po obecności `:` w adresie.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.contains(':') {
+            s.parse().map(IpPrefix::V6)
+        } else {
+            s.parse().map(IpPrefix::V4)
+        }
+    }
+}
+
+impl From<IPv4Prefix> for IpPrefix {
+    fn from(p: IPv4Prefix) -> Self {
+        IpPrefix::V4(p)
+    }
+}
+
+impl From<IPv6Prefix> for IpPrefix {
+    fn from(p: IPv6Prefix) -> Self {
+        IpPrefix::V6(p)
+    }
+}
+
+impl IpPrefix {
+    /// Parsuje prefiks z dowolnej rodziny, stosując `policy` do bitów hosta
+    /// (zob. [`IPv6Prefix::parse_with`]).
+    pub fn parse_with(
+        s: &str,
+        policy: HostBitsPolicy,
+    ) -> Result<(Self, Option<HostBitsWarning<Self>>), PrefixParseError> {
+        fn widen<P: Into<IpPrefix>>(
+            (p, w): (P, Option<HostBitsWarning<P>>),
+        ) -> (IpPrefix, Option<HostBitsWarning<IpPrefix>>) {
+            let w = w.map(|w| HostBitsWarning { given: w.given.into(), network: w.network.into() });
+            (p.into(), w)
+        }
+        if s.contains(':') {
+            IPv6Prefix::parse_with(s, policy).map(widen)
+        } else {
+            IPv4Prefix::parse_with(s, policy).map(widen)
+        }
+    }
+
+    /// Rodzina adresów prefiksu.
+    pub fn family(&self) -> Family {
+        match self {
+            IpPrefix::V4(_) => Family::V4,
+            IpPrefix::V6(_) => Family::V6,
+        }
+    }
+
+    /// Długość maski w bitach.
+    pub fn prefix_len(&self) -> u8 {
+        match self {
+            IpPrefix::V4(p) => p.prefix_len(),
+            IpPrefix::V6(p) => p.prefix_len(),
+        }
+    }
+
+    /// Czy adres nie ma ustawionych bitów za maską?
+    pub fn is_canonical(&self) -> bool {
+        match self {
+            IpPrefix::V4(p) => p.is_canonical(),
+            IpPrefix::V6(p) => p.is_canonical(),
+        }
+    }
+
+    /// Ten sam prefiks z adresem sieci.
+    pub fn normalized(&self) -> Self {
+        match self {
+            IpPrefix::V4(p) => IpPrefix::V4(p.normalized()),
+            IpPrefix::V6(p) => IpPrefix::V6(p.normalized()),
+        }
+    }
+
+    /// Czy adres leży w prefiksie? Adres z innej rodziny nigdy nie leży.
+    pub fn contains_addr(&self, addr: IpAddr) -> bool {
+        match (self, addr) {
+            (IpPrefix::V4(p), IpAddr::V4(a)) => p.contains_addr(a.into()),
+            (IpPrefix::V6(p), IpAddr::V6(a)) => p.contains_addr(a.into()),
+            _ => false,
+        }
+    }
+
+    /// Czy dwa prefiksy mają wspólny fragment? Błąd gdy rodziny się różnią.
+    pub fn overlaps(&self, other: &Self) -> Result<bool, FamilyMismatch> {
+        match (self, other) {
+            (IpPrefix::V4(a), IpPrefix::V4(b)) => Ok(a.overlaps(b)),
+            (IpPrefix::V6(a), IpPrefix::V6(b)) => Ok(a.overlaps(b)),
+            _ => Err(FamilyMismatch),
+        }
+    }
+
+    /// Relacja między prefiksami. Błąd gdy rodziny się różnią.
+    pub fn relation(&self, other: &Self) -> Result<PrefixRelation, FamilyMismatch> {
+        match (self, other) {
+            (IpPrefix::V4(a), IpPrefix::V4(b)) => Ok(a.relation(b)),
+            (IpPrefix::V6(a), IpPrefix::V6(b)) => Ok(a.relation(b)),
+            _ => Err(FamilyMismatch),
+        }
+    }
+}
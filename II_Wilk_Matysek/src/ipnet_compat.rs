@@ -0,0 +1,48 @@
+//! Konwersje do typów z biblioteki `ipnet` (feature `ipnet`).
+
+use ipnet::{IpNet, Ipv4Net, Ipv6Net};
+
+use crate::{IPv4Prefix, IPv6Prefix, IpPrefix};
+
+/// Adres przechodzi bez maskowania, tak jak w `Ipv6Net::new`.
+impl From<IPv6Prefix> for Ipv6Net {
+    fn from(p: IPv6Prefix) -> Self {
+        Ipv6Net::new(p.addr().into(), p.prefix_len()).expect("len <= 128")
+    }
+}
+
+impl From<Ipv6Net> for IPv6Prefix {
+    fn from(n: Ipv6Net) -> Self {
+        IPv6Prefix::new(n.addr().into(), n.prefix_len()).expect("len <= 128")
+    }
+}
+
+impl From<IPv4Prefix> for Ipv4Net {
+    fn from(p: IPv4Prefix) -> Self {
+        Ipv4Net::new(p.addr().into(), p.prefix_len()).expect("len <= 32")
+    }
+}
+
+impl From<Ipv4Net> for IPv4Prefix {
+    fn from(n: Ipv4Net) -> Self {
+        IPv4Prefix::new(n.addr().into(), n.prefix_len()).expect("len <= 32")
+    }
+}
+
+impl From<IpPrefix> for IpNet {
+    fn from(p: IpPrefix) -> Self {
+        match p {
+            IpPrefix::V4(p) => IpNet::V4(p.into()),
+            IpPrefix::V6(p) => IpNet::V6(p.into()),
+        }
+    }
+}
+
+impl From<IpNet> for IpPrefix {
+    fn from(n: IpNet) -> Self {
+        match n {
+            IpNet::V4(n) => IpPrefix::V4(n.into()),
+            IpNet::V6(n) => IpPrefix::V6(n.into()),
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use std::{
+    fmt,
+    ops::{BitAnd, BitOr, Not},
+    str::FromStr,
+};
+
+use crate::{
+    HostBitsPolicy, HostBitsWarning, IPv6Addr, IPv6Prefix, PrefixParseError, PrefixRelation,
+    error::parse_len,
+};
+
+/// 32 bitowy adres IPv4.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub struct IPv4Addr(pub u32);
+
+impl BitAnd for IPv4Addr {
+    type Output = Self;
+    fn bitand(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+}
+
+impl BitOr for IPv4Addr {
+    type Output = Self;
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl Not for IPv4Addr {
+    type Output = Self;
+    fn not(self) -> Self {
+        Self(!self.0)
+    }
+}
+
+impl fmt::Display for IPv4Addr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c, d] = self.0.to_be_bytes();
+        f.pad(&format!("{a}.{b}.{c}.{d}"))
+    }
+}
+
+impl FromStr for IPv4Addr {
+    type Err = PrefixParseError;
+
+    /// Adres w notacji kropkowo-dziesiętnej, np. `192.0.2.1`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.split('.').count() != 4 {
+            return Err(PrefixParseError::BadOctetCount { pos: 0 });
+        }
+        let mut bits = 0u32;
+        let mut pos = 0;
+        for (index, o) in s.split('.').enumerate() {
+            let digits_ok = !o.is_empty()
+                && o.len() <= 3
+                && o.bytes().all(|b| b.is_ascii_digit())
+                && (o == "0" || !o.starts_with('0'));
+            let v: u8 = match o.parse() {
+                Ok(v) if digits_ok => v,
+                _ => return Err(PrefixParseError::BadOctet { index, pos }),
+            };
+            bits = (bits << 8) | v as u32;
+            pos += o.len() + 1;
+        }
+        Ok(IPv4Addr(bits))
+    }
+}
+
+/// Prefiks IPv4 w postaci adres + długość maski.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct IPv4Prefix {
+    addr: IPv4Addr,
+    len: u8,
+}
+
+impl fmt::Display for IPv4Prefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&format!("{}/{}", self.addr, self.len))
+    }
+}
+
+impl FromStr for IPv4Prefix {
+    type Err = PrefixParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (ip_str, len_str) = s.split_once('/').ok_or(PrefixParseError::MissingSlash)?;
+        let len = parse_len(len_str, ip_str.len() + 1, 32)?;
+        Ok(IPv4Prefix { addr: ip_str.parse()?, len })
+    }
+}
+
+impl IPv4Prefix {
+    /// Tworzy prefiks; `None` gdy długość maski > 32.
+    pub fn new(addr: IPv4Addr, len: u8) -> Option<Self> {
+        (len <= 32).then_some(Self { addr, len })
+    }
+
+    /// Parsuje prefiks, stosując `policy` do bitów hosta, jak [`IPv6Prefix::parse_with`].
+    pub fn parse_with(
+        s: &str,
+        policy: HostBitsPolicy,
+    ) -> Result<(Self, Option<HostBitsWarning<Self>>), PrefixParseError> {
+        let given: Self = s.parse()?;
+        if given.is_canonical() {
+            return Ok((given, None));
+        }
+        let warning = HostBitsWarning { given, network: given.normalized() };
+        match policy {
+            HostBitsPolicy::Strict => Err(PrefixParseError::HostBitsSet),
+            HostBitsPolicy::Normalize => Ok((warning.network, Some(warning))),
+            HostBitsPolicy::Warn => Ok((given, Some(warning))),
+        }
+    }
+
+    /// Adres podany przy tworzeniu prefiksu (bez maskowania).
+    pub fn addr(&self) -> IPv4Addr {
+        self.addr
+    }
+
+    /// Adres sieci, czyli `addr` z wyzerowanymi bitami hosta.
+    pub fn network(&self) -> IPv4Addr {
+        self.addr & self.mask()
+    }
+
+    /// Czy adres nie ma ustawionych bitów za maską?
+    pub fn is_canonical(&self) -> bool {
+        self.addr == self.network()
+    }
+
+    /// Ten sam prefiks z adresem sieci zamiast `addr`.
+    pub fn normalized(&self) -> Self {
+        Self { addr: self.network(), len: self.len }
+    }
+
+    /// Długość maski w bitach (0..=32).
+    pub fn prefix_len(&self) -> u8 {
+        self.len
+    }
+
+    /// Maska bitowa jako IPv4Addr
+    pub fn mask(&self) -> IPv4Addr {
+        // przesunięcie o 32 bity spanikowałoby
+        if self.len == 0 {
+            return IPv4Addr(0);
+        }
+        IPv4Addr((!0u32) << (32 - self.len))
+    }
+
+    /// Zwraca (pierwszy_adres, ostatni_adres) w prefiksie
+    pub fn range(&self) -> (IPv4Addr, IPv4Addr) {
+        let m = self.mask();
+        let net = self.addr & m;
+        (net, net | !m)
+    }
+
+    /// Maska hosta (odwrotność [`mask`](Self::mask)): jedynki na bitach hosta.
+    pub fn hostmask(&self) -> IPv4Addr {
+        !self.mask()
+    }
+
+    /// Liczba adresów, 2^(32 − długość).
+    pub fn address_count(&self) -> u64 {
+        1u64 << (32 - self.len)
+    }
+
+    /// Ten sam prefiks w przestrzeni `::ffff:0:0/96` (długość maski + 96).
+    pub fn to_ipv6_mapped(&self) -> IPv6Prefix {
+        IPv6Prefix::new(IPv6Addr::from_ipv4_mapped(self.addr), self.len + 96)
+            .expect("len <= 32")
+    }
+
+    /// Czy adres leży w prefiksie?
+    pub fn contains_addr(&self, addr: IPv4Addr) -> bool {
+        addr & self.mask() == self.network()
+    }
+
+    /// Czy dwa prefiksy mają wspólny fragment?
+    pub fn overlaps(&self, other: &Self) -> bool {
+        let (s1, e1) = self.range();
+        let (s2, e2) = other.range();
+        s1 <= e2 && s2 <= e1
+    }
+
+    /// Jak `self` ma się do `other`: równość, zawieranie, sąsiedztwo albo rozłączność.
+    pub fn relation(&self, other: &Self) -> PrefixRelation {
+        let siblings =
+            self.len == other.len && self.len > 0 && self.supernet() == other.supernet();
+        PrefixRelation::from_ranges(self.range(), other.range(), siblings)
+    }
+
+    /// Nadprefiks o długości `len - 1`; `None` dla `/0`.
+    pub fn supernet(&self) -> Option<Self> {
+        self.to_ipv6_mapped().supernet()?.to_ipv4_mapped()
+    }
+
+    /// Nadprefiks o długości `len`; `None` gdy `len` jest większe od długości `self`.
+    pub fn supernet_of_len(&self, len: u8) -> Option<Self> {
+        (len <= self.len).then(|| Self { addr: self.addr, len }.normalized())
+    }
+
+    /// Druga połówka wspólnego nadprefiksu; `None` dla `/0`.
+    pub fn sibling(&self) -> Option<Self> {
+        self.to_ipv6_mapped().sibling()?.to_ipv4_mapped()
+    }
+
+    /// Dwie połówki prefiksu (lewa, prawa); `None` dla `/32`.
+    pub fn children(&self) -> Option<(Self, Self)> {
+        let (l, r) = self.to_ipv6_mapped().children()?;
+        Some((l.to_ipv4_mapped()?, r.to_ipv4_mapped()?))
+    }
+
+    /// Czy prefiks jest lewą (niższą) połówką nadprefiksu? Dla `/0` zawsze `false`.
+    pub fn is_left_child(&self) -> bool {
+        self.len > 0 && self.to_ipv6_mapped().is_left_child()
+    }
+}
@@ -0,0 +1,48 @@
+use std::fmt;
+
+/// Język komunikatów. `Display` typów z biblioteki używa angielskiego;
+/// inny język wybiera się przez [`Localize::in_lang`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Lang {
+    #[default]
+    En,
+    Pl,
+}
+
+impl Lang {
+    /// Język z kodu (`en`, `pl`) albo z locale w stylu `LANG`, np. `pl_PL.UTF-8`.
+    ///
+    /// `None` dla nieobsługiwanych języków oraz locale `C`/`POSIX`.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let code = tag.split(['_', '-', '.', '@']).next().unwrap_or("");
+        match code.to_ascii_lowercase().as_str() {
+            "en" => Some(Lang::En),
+            "pl" => Some(Lang::Pl),
+            _ => None,
+        }
+    }
+}
+
+/// Tekst zależny od języka; `Display` daje wersję angielską.
+pub trait Localize {
+    /// Wypisuje komunikat w języku `lang`.
+    fn fmt_in(&self, lang: Lang, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+
+    /// Obiekt, który wyświetla się w języku `lang`.
+    fn in_lang(&self, lang: Lang) -> InLang<'_, Self> {
+        InLang { inner: self, lang }
+    }
+}
+
+/// Wynik [`Localize::in_lang`].
+#[derive(Copy, Clone, Debug)]
+pub struct InLang<'a, T: ?Sized> {
+    inner: &'a T,
+    lang: Lang,
+}
+
+impl<T: Localize + ?Sized> fmt::Display for InLang<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt_in(self.lang, f)
+    }
+}
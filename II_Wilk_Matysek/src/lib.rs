@@ -0,0 +1,62 @@
+//! Operacje na adresach i prefiksach IPv6 oraz IPv4.
+//!
+//! ```
+//! use ii_wilk_matysek::{IPv6Prefix, IpPrefix};
+//!
+//! let a: IPv6Prefix = "2001:db8::/32".parse().unwrap();
+//! let b: IPv6Prefix = "2001:db8:1::/48".parse().unwrap();
+//! assert!(a.overlaps(&b));
+//!
+//! let c: IpPrefix = "10.0.0.0/8".parse().unwrap();
+//! let d: IpPrefix = "10.1.0.0/16".parse().unwrap();
+//! assert_eq!(c.overlaps(&d), Ok(true));
+//! ```
+//!
+//! Adresy konwertują się z/do `std::net`, a z feature `ipnet` prefiksy
+//! także z/do `ipnet::Ipv6Net`, `Ipv4Net` i `IpNet`. Feature `serde` dodaje
+//! serializację adresów i prefiksów.
+//!
+//! Nazwy odwrotne DNS: [`IPv6Addr::to_ptr_name`], [`IPv6Prefix::to_ip6_arpa`]
+//! oraz parsowanie w drugą stronę ([`IPv6Addr::from_ptr_name`],
+//! [`IPv6Prefix::from_ip6_arpa`]).
+//!
+//! Komunikaty (`Display` błędów, relacji i rodzajów adresów) są po
+//! angielsku; wersję polską daje [`Localize::in_lang`] z [`Lang::Pl`].
+//! Rodzaj błędu rozpoznaje się po wariancie, nie po treści komunikatu.
+
+mod addr;
+mod aggregate;
+mod arpa;
+mod classify;
+mod convert;
+mod error;
+mod exclude;
+mod ip;
+mod ipv4;
+#[cfg(feature = "ipnet")]
+mod ipnet_compat;
+mod lang;
+mod overlap;
+mod prefix;
+mod range;
+mod relation;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod set;
+mod subnets;
+mod table;
+
+pub use addr::IPv6Addr;
+pub use classify::{AddrClass, MulticastScope};
+pub use convert::AddrFamilyMismatch;
+pub use error::PrefixParseError;
+pub use ip::{Family, FamilyMismatch, IpPrefix};
+pub use ipv4::{IPv4Addr, IPv4Prefix};
+pub use lang::{InLang, Lang, Localize};
+pub use overlap::find_overlaps;
+pub use prefix::{HostBitsPolicy, HostBitsWarning, IPv6Prefix};
+pub use range::{range_to_prefixes, range_to_prefixes_v4};
+pub use relation::PrefixRelation;
+pub use set::PrefixSet;
+pub use subnets::{Subnets, SubnetsV4};
+pub use table::PrefixTable;
@@ -1,127 +1,947 @@
+mod messages;
+
 use std::{
+    collections::HashSet,
     env,
-    ops::{BitAnd, BitOr, Not},
-    str::FromStr,
+    fmt::Display,
+    fs,
+    io::{self, Read, Write},
+    net::IpAddr,
+    process::ExitCode,
+};
+
+use ii_wilk_matysek::{
+    AddrClass, HostBitsPolicy, IPv4Addr, IPv6Addr, IPv6Prefix, IpPrefix, Lang, Localize,
+    PrefixRelation, find_overlaps,
 };
+use messages::{Msg, Text};
+
+/// Kod wyjścia niosący wynik sprawdzenia: `overlap`, gdy coś się nakłada,
+/// i `contains`, gdy adresu nie ma w żadnym prefiksie.
+const EXIT_FLAG: u8 = 1;
+/// Kod wyjścia przy błędnym wywołaniu albo danych.
+const EXIT_ERROR: u8 = 2;
+
+/// Domyślny limit podsieci wypisywanych przez `split`.
+const SPLIT_DEFAULT_COUNT: usize = 1024;
+
+/// 2^128, liczba adresów w `::/0`; nie mieści się w `u128`.
+const IPV6_SPACE_SIZE: &str = "340282366920938463463374607431768211456";
+
+struct Command {
+    name: &'static str,
+    args: Text,
+    about: Text,
+    /// Opcje z wartością, które polecenie przyjmuje.
+    options: &'static [&'static str],
+}
+
+const COMMANDS: &[Command] = &[
+    Command {
+        name: "overlap",
+        args: Text { en: "<prefix>... | -f <file>", pl: "<prefiks>... | -f <plik>" },
+        about: Text {
+            en: "Whether the prefixes overlap; for two prints ‘yes’/‘no’ and the relation,\n\
+                 for a longer list every overlapping pair. The default command.",
+            pl: "Czy prefiksy się nakładają; dla dwóch wypisuje ‘tak’/‘nie’ i relację,\n\
+                 dla dłuższej listy każdą nakładającą się parę. Polecenie domyślne.",
+        },
+        options: &["--file"],
+    },
+    Command {
+        name: "contains",
+        args: Text {
+            en: "<prefix>... | -f <file> <address>",
+            pl: "<prefiks>... | -f <plik> <adres>",
+        },
+        about: Text {
+            en: "Prints the prefixes that contain the address.",
+            pl: "Wypisuje prefiksy, w których leży adres.",
+        },
+        options: &["--file"],
+    },
+    Command {
+        name: "info",
+        args: Text { en: "<prefix>... | -f <file>", pl: "<prefiks>... | -f <plik>" },
+        about: Text {
+            en: "Prefix details: address range, masks, address count, reverse zone\n\
+                 and address space class.",
+            pl: "Szczegóły prefiksu: zakres adresów, maski, liczba adresów, strefa\n\
+                 odwrotna i rodzaj przestrzeni.",
+        },
+        options: &["--file"],
+    },
+    Command {
+        name: "split",
+        args: Text {
+            en: "<prefix> <length> [--skip N] [--count N]",
+            pl: "<prefiks> <długość> [--skip N] [--count N]",
+        },
+        about: Text {
+            en: "Splits the prefix into subnets of the given length.",
+            pl: "Dzieli prefiks na podsieci o podanej długości.",
+        },
+        options: &["--skip", "--count"],
+    },
+    Command {
+        name: "aggregate",
+        args: Text { en: "<prefix>... | -f <file>", pl: "<prefiks>... | -f <plik>" },
+        about: Text {
+            en: "Shortest list of prefixes covering the same addresses.",
+            pl: "Najkrótsza lista prefiksów pokrywająca te same adresy.",
+        },
+        options: &["--file"],
+    },
+    Command {
+        name: "exclude",
+        args: Text {
+            en: "<pool> <excluded>... | -f <file>",
+            pl: "<pula> <wykluczony>... | -f <plik>",
+        },
+        about: Text {
+            en: "Prefixes covering the pool without the excluded addresses.",
+            pl: "Prefiksy pokrywające pulę bez wykluczonych adresów.",
+        },
+        options: &["--file"],
+    },
+];
 
-/// 128 bitowy adres IPv6, przechowywany jako dwa u64.
-#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
-struct IPv6Addr {
-    high: u64,
-    low: u64,
+/// Opcja z wartością.
+struct OptSpec {
+    long: &'static str,
+    short: Option<&'static str>,
+    arg: Text,
+    about: Text,
 }
 
-impl BitAnd for IPv6Addr {
-    type Output = Self;
-    fn bitand(self, other: Self) -> Self {
-        Self { high: self.high & other.high, low: self.low & other.low }
+const OPTIONS: &[OptSpec] = &[
+    OptSpec {
+        long: "--file",
+        short: Some("-f"),
+        arg: Text { en: "<file>", pl: "<plik>" },
+        about: Text {
+            en: "read prefixes from a file, one per line (`-` = stdin)",
+            pl: "prefiksy z pliku, po jednym w wierszu (`-` = stdin)",
+        },
+    },
+    OptSpec {
+        long: "--skip",
+        short: None,
+        arg: Text { en: "N", pl: "N" },
+        about: Text { en: "skip the first N subnets", pl: "pomiń pierwsze N podsieci" },
+    },
+    OptSpec {
+        long: "--count",
+        short: None,
+        arg: Text { en: "N", pl: "N" },
+        about: Text {
+            en: "print at most N subnets (default 1024)",
+            pl: "wypisz co najwyżej N podsieci (domyślnie 1024)",
+        },
+    },
+    OptSpec {
+        long: "--output",
+        short: Some("-o"),
+        arg: Text { en: "<format>", pl: "<format>" },
+        about: Text {
+            en: "text (default), json or csv",
+            pl: "text (domyślnie), json albo csv",
+        },
+    },
+    OptSpec {
+        long: "--lang",
+        short: None,
+        arg: Text { en: "<lang>", pl: "<język>" },
+        about: Text {
+            en: "message language: en (default) or pl; otherwise from LANG",
+            pl: "język komunikatów: en (domyślnie) albo pl; bez opcji z LANG",
+        },
+    },
+];
+
+/// Opcje przyjmowane przez każde polecenie.
+const COMMON_OPTIONS: &[&str] = &["--output", "--lang"];
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl Format {
+    fn parse(s: &str) -> Result<Self, CliError> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(CliError::Usage(Msg::BadFormat(s.to_string()))),
+        }
     }
 }
 
-impl BitOr for IPv6Addr {
-    type Output = Self;
-    fn bitor(self, other: Self) -> Self {
-        Self { high: self.high | other.high, low: self.low | other.low }
+enum CliError {
+    /// Błędne wywołanie: nieznane polecenie, opcja albo brak argumentów.
+    Usage(Msg),
+    /// Błędne dane: prefiks, adres, zakres albo nieczytelny plik.
+    Input(Msg),
+    /// Błąd zapisu na stdout.
+    Output(io::Error),
+}
+
+impl From<io::Error> for CliError {
+    fn from(e: io::Error) -> Self {
+        CliError::Output(e)
     }
 }
 
-impl Not for IPv6Addr {
-    type Output = Self;
-    fn not(self) -> Self {
-        Self { high: !self.high, low: !self.low }
+type CliResult = Result<ExitCode, CliError>;
+
+fn help(prog: &str, lang: Lang) -> String {
+    let usage = messages::HELP_USAGE.get(lang);
+    let mut s = format!(
+        "{}\n\n{usage}: {prog} {}\n{:indent$}{prog} {}\n\n{}:\n",
+        messages::HELP_INTRO.get(lang),
+        messages::HELP_CMD_ARGS.get(lang),
+        "",
+        messages::HELP_DEFAULT_CMD.get(lang),
+        messages::HELP_COMMANDS.get(lang),
+        indent = usage.chars().count() + 2,
+    );
+    for c in COMMANDS {
+        s += &format!("  {:<10} {}\n", c.name, c.args.get(lang));
     }
+    s += &format!("  {:<10} {}\n", "help", messages::HELP_HELP_ARGS.get(lang));
+    s += &format!("\n{}:\n", messages::HELP_OPTIONS.get(lang));
+    s += &option_lines(OPTIONS.iter(), lang);
+    s += &format!("  {:<22} {}\n", "-h, --help", messages::HELP_THIS.get(lang));
+    s += &format!("  {:<22} {}\n", "-V, --version", messages::HELP_VERSION.get(lang));
+    s += "\n";
+    s += messages::HELP_TAIL.get(lang);
+    s
 }
 
-/// Prefiks IPv6 w postaci adres + długość maski.
-#[derive(Debug)]
-struct IPv6Prefix {
-    addr: IPv6Addr,
-    len: u8,
+fn command_help(prog: &str, c: &Command, lang: Lang) -> String {
+    let mut s = format!(
+        "{}: {prog} {} {}\n\n{}\n\n{}:\n",
+        messages::HELP_USAGE.get(lang),
+        c.name,
+        c.args.get(lang),
+        c.about.get(lang),
+        messages::HELP_OPTIONS.get(lang),
+    );
+    s += &option_lines(OPTIONS.iter().filter(|o| accepts(c, o.long)), lang);
+    s += &format!("  {:<22} {}\n", "-h, --help", messages::HELP_THIS.get(lang));
+    s
 }
 
-impl FromStr for IPv6Prefix {
-    type Err = String;
+fn option_lines<'a>(opts: impl Iterator<Item = &'a OptSpec>, lang: Lang) -> String {
+    let mut s = String::new();
+    for o in opts {
+        let flag = match o.short {
+            Some(short) => format!("{short}, {} {}", o.long, o.arg.get(lang)),
+            None => format!("{} {}", o.long, o.arg.get(lang)),
+        };
+        s += &format!("  {flag:<22} {}\n", o.about.get(lang));
+    }
+    s
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (ip_str, len_str) =
-            s.split_once('/').ok_or("Brak ‘/’ w prefiksie".to_string())?;
-        let len: u8 = len_str.parse()
-            .map_err(|_| "Niepoprawna długosc maski".to_string())?;
-        if len > 128 { return Err("Maska > 128".into()); }
+fn accepts(cmd: &Command, long: &str) -> bool {
+    cmd.options.contains(&long) || COMMON_OPTIONS.contains(&long)
+}
 
-        let parts: Vec<&str> = ip_str.split("::").collect();
-        if parts.len() > 2 { return Err("Za duzo ‘::’".into()); }
+/// Argumenty polecenia po rozdzieleniu opcji od pozycyjnych.
+#[derive(Default)]
+struct Opts {
+    positional: Vec<String>,
+    file: Option<String>,
+    skip: Option<usize>,
+    count: Option<usize>,
+    format: Format,
+    lang: Lang,
+}
 
-        let head = if parts[0].is_empty() { vec![] } else { parts[0].split(':').collect() };
-        let tail = if parts.len()==2 && !parts[1].is_empty() {
-            parts[1].split(':').collect()
-        } else { vec![] };
+impl Opts {
+    fn parse(cmd: &Command, args: &[String], lang: Lang) -> Result<Self, CliError> {
+        let mut opts = Opts { lang, ..Opts::default() };
+        let mut it = args.iter();
+        while let Some(arg) = it.next() {
+            // prefiksy i zakresy nigdy nie zaczynają się od `-`, samo `-` to stdin
+            if !arg.starts_with('-') || arg == "-" {
+                opts.positional.push(arg.clone());
+                continue;
+            }
+            let (flag, inline) = match arg.split_once('=') {
+                Some((f, v)) => (f, Some(v.to_string())),
+                None => (arg.as_str(), None),
+            };
+            let long = OPTIONS
+                .iter()
+                .find(|o| o.long == flag || o.short == Some(flag))
+                .map(|o| o.long)
+                .filter(|long| accepts(cmd, long))
+                .ok_or_else(|| {
+                    CliError::Usage(Msg::UnknownOption { flag: flag.to_string(), cmd: cmd.name })
+                })?;
+            let Some(value) = inline.or_else(|| it.next().cloned()) else {
+                return Err(CliError::Usage(Msg::MissingValue(long)));
+            };
+            match long {
+                "--file" => opts.file = Some(value),
+                "--skip" => opts.skip = Some(parse_number(&value, "--skip")?),
+                "--count" => opts.count = Some(parse_number(&value, "--count")?),
+                "--output" => opts.format = Format::parse(&value)?,
+                // `--lang` zdejmuje wcześniej `take_lang`
+                _ => unreachable!("opcja z OPTIONS bez obsługi"),
+            }
+        }
+        Ok(opts)
+    }
 
-        if head.len() + tail.len() > 8 {
-            return Err("Zbyt wiele segmentow IPv6".into());
+    /// Prefiksy z `items` (`-` to lista ze stdin), a potem z pliku `--file`, jeśli podano.
+    fn prefixes(&self, items: &[String]) -> Result<Vec<IpPrefix>, CliError> {
+        let mut prefixes = Vec::new();
+        for s in items {
+            if s == "-" {
+                prefixes.extend(read_list(s, self.lang)?);
+            } else {
+                prefixes.extend(parse_item(s, "", self.lang)?);
+            }
         }
+        if let Some(path) = &self.file {
+            prefixes.extend(read_list(path, self.lang)?);
+        }
+        Ok(prefixes)
+    }
+}
 
-        let mut segs = Vec::with_capacity(8);
-        for h in &head {
-            segs.push(u16::from_str_radix(h, 16)
-                .map_err(|_| "Bledny segment IPv6".to_string())?);
+fn parse_number(s: &str, option: &'static str) -> Result<usize, CliError> {
+    s.parse().map_err(|_| CliError::Usage(Msg::BadNumber { option, value: s.to_string() }))
+}
+
+fn parse_lang(s: &str) -> Result<Lang, CliError> {
+    Lang::from_tag(s).ok_or_else(|| CliError::Usage(Msg::BadLang(s.to_string())))
+}
+
+/// Język z LC_ALL, LC_MESSAGES albo LANG (pierwsza niepusta zmienna, jak w POSIX).
+fn env_lang() -> Lang {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .find_map(|var| env::var(var).ok().filter(|v| !v.is_empty()))
+        .and_then(|v| Lang::from_tag(&v))
+        .unwrap_or_default()
+}
+
+/// Zdejmuje z argumentów `--lang <język>` (albo `--lang=<język>`), żeby opcja
+/// działała w dowolnym miejscu, także przed nazwą polecenia.
+fn take_lang(args: &mut Vec<String>, lang: Lang) -> Result<Lang, CliError> {
+    let Some(i) = args.iter().position(|a| a == "--lang" || a.starts_with("--lang=")) else {
+        return Ok(lang);
+    };
+    let arg = args.remove(i);
+    if let Some(value) = arg.strip_prefix("--lang=") {
+        return parse_lang(value);
+    }
+    if i == args.len() {
+        return Err(CliError::Usage(Msg::MissingValue("--lang")));
+    }
+    parse_lang(&args.remove(i))
+}
+
+/// `origin` poprzedza komunikat o błędzie, np. `plik:3: `.
+fn parse_arg(s: &str, origin: &str, lang: Lang) -> Result<IpPrefix, CliError> {
+    let (p, warning) = IpPrefix::parse_with(s, HostBitsPolicy::Warn).map_err(|err| {
+        CliError::Input(Msg::BadPrefix { origin: origin.to_string(), input: s.to_string(), err })
+    })?;
+    if let Some(warning) = warning {
+        let msg = Msg::HostBits { origin: origin.to_string(), warning };
+        eprintln!("{}", msg.in_lang(lang));
+    }
+    Ok(p)
+}
+
+/// Prefiks albo zakres `pierwszy-ostatni` rozpisany na prefiksy.
+fn parse_item(s: &str, origin: &str, lang: Lang) -> Result<Vec<IpPrefix>, CliError> {
+    if !s.contains('-') {
+        return Ok(vec![parse_arg(s, origin, lang)?]);
+    }
+    IpPrefix::parse_range(s).map_err(|err| {
+        CliError::Input(Msg::BadRange { origin: origin.to_string(), input: s.to_string(), err })
+    })
+}
+
+fn parse_addr(s: &str) -> Result<IpAddr, CliError> {
+    if s.contains(':') {
+        s.parse::<IPv6Addr>().map(Into::into)
+    } else {
+        s.parse::<IPv4Addr>().map(Into::into)
+    }
+    .map_err(|err| CliError::Input(Msg::BadAddr { input: s.to_string(), err }))
+}
+
+/// Czyta prefiksy z pliku (albo stdin dla `-`), pomijając puste wiersze i komentarze `#`.
+fn read_list(path: &str, lang: Lang) -> Result<Vec<IpPrefix>, CliError> {
+    let text = if path == "-" {
+        let mut buf = String::new();
+        io::stdin().read_to_string(&mut buf).map(|_| buf)
+    } else {
+        fs::read_to_string(path)
+    };
+    let text =
+        text.map_err(|err| CliError::Input(Msg::Unreadable { path: path.to_string(), err }))?;
+
+    let mut prefixes = Vec::new();
+    for (n, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if !line.is_empty() {
+            prefixes.extend(parse_item(line, &format!("{path}:{}: ", n + 1), lang)?);
+        }
+    }
+    Ok(prefixes)
+}
+
+/// Wartość w rekordzie wyjścia `json`/`csv`.
+enum Value {
+    Null,
+    Bool(bool),
+    Num(u64),
+    Str(String),
+    /// Pola w stałej kolejności; w CSV spłaszczane do kolumn `rodzic_pole`.
+    Obj(Vec<(&'static str, Value)>),
+}
+
+fn str_value(s: impl Display) -> Value {
+    Value::Str(s.to_string())
+}
+
+fn write_json_str(out: &mut dyn Write, s: &str) -> io::Result<()> {
+    out.write_all(b"\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => out.write_all(b"\\\"")?,
+            '\\' => out.write_all(b"\\\\")?,
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32)?,
+            c => write!(out, "{c}")?,
         }
-        for _ in 0..(8 - head.len() - tail.len()) { segs.push(0); }
-        for t in &tail {
-            segs.push(u16::from_str_radix(t, 16)
-                .map_err(|_| "Bledny segment IPv6".to_string())?);
+    }
+    out.write_all(b"\"")
+}
+
+fn write_json(out: &mut dyn Write, v: &Value) -> io::Result<()> {
+    match v {
+        Value::Null => out.write_all(b"null"),
+        Value::Bool(b) => write!(out, "{b}"),
+        Value::Num(n) => write!(out, "{n}"),
+        Value::Str(s) => write_json_str(out, s),
+        Value::Obj(fields) => {
+            out.write_all(b"{")?;
+            for (n, (key, v)) in fields.iter().enumerate() {
+                if n > 0 {
+                    out.write_all(b",")?;
+                }
+                write_json_str(out, key)?;
+                out.write_all(b":")?;
+                write_json(out, v)?;
+            }
+            out.write_all(b"}")
         }
+    }
+}
 
-        let high = ((segs[0] as u64) << 48)
-            | ((segs[1] as u64) << 32)
-            | ((segs[2] as u64) << 16)
-            | (segs[3] as u64);
-        let low  = ((segs[4] as u64) << 48)
-            | ((segs[5] as u64) << 32)
-            | ((segs[6] as u64) << 16)
-            | (segs[7] as u64);
+/// Spłaszcza rekord do par (kolumna, tekst) dla CSV.
+fn flatten(prefix: &str, v: &Value, cols: &mut Vec<(String, String)>) {
+    let cell = match v {
+        Value::Null => String::new(),
+        Value::Bool(b) => b.to_string(),
+        Value::Num(n) => n.to_string(),
+        Value::Str(s) => s.clone(),
+        Value::Obj(fields) => {
+            for (key, v) in fields {
+                let name = match prefix {
+                    "" => key.to_string(),
+                    _ => format!("{prefix}_{key}"),
+                };
+                flatten(&name, v, cols);
+            }
+            return;
+        }
+    };
+    cols.push((prefix.to_string(), cell));
+}
 
-        Ok(IPv6Prefix { addr: IPv6Addr { high, low }, len })
+fn csv_cell(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
     }
 }
 
-impl IPv6Prefix {
-    /// Maska bitowa jako IPv6Addr
-    fn mask(&self) -> IPv6Addr {
-        let m = self.len;
-        // unikamy paniki
-        if m == 0 {
-            return IPv6Addr { high: 0, low: 0 };
+/// Wypisuje rekordy jako tablicę JSON (rekord w wierszu) albo CSV z nagłówkiem.
+///
+/// Nagłówek CSV pochodzi z pierwszego rekordu, więc bez rekordów wyjście jest puste.
+fn write_records(out: &mut dyn Write, format: Format, records: &[Value]) -> io::Result<()> {
+    match format {
+        Format::Json => {
+            out.write_all(b"[")?;
+            for (n, r) in records.iter().enumerate() {
+                out.write_all(if n > 0 { b",\n  " } else { b"\n  " })?;
+                write_json(out, r)?;
+            }
+            out.write_all(if records.is_empty() { b"]\n" } else { b"\n]\n" })
         }
-        let high = if m >= 64 { u64::MAX } else { (!0u64) << (64 - m) };
-        let low  = if m <= 64 { 0 } else { (!0u64) << (128 - m) };
-        IPv6Addr { high, low }
+        Format::Csv => {
+            for (n, r) in records.iter().enumerate() {
+                let mut cols = Vec::new();
+                flatten("", r, &mut cols);
+                if n == 0 {
+                    let header: Vec<_> = cols.iter().map(|(k, _)| csv_cell(k)).collect();
+                    writeln!(out, "{}", header.join(","))?;
+                }
+                let row: Vec<_> = cols.iter().map(|(_, v)| csv_cell(v)).collect();
+                writeln!(out, "{}", row.join(","))?;
+            }
+            Ok(())
+        }
+        Format::Text => unreachable!("tekst wypisują same polecenia"),
     }
+}
+
+/// Pierwszy i ostatni adres prefiksu.
+fn range_value(p: &IpPrefix) -> Value {
+    let (first, last) = match p {
+        IpPrefix::V6(v6) => (str_value(v6.range().0), str_value(v6.range().1)),
+        IpPrefix::V4(v4) => (str_value(v4.range().0), str_value(v4.range().1)),
+    };
+    Value::Obj(vec![("first", first), ("last", last)])
+}
 
-    /// Zwraca (pierwszy_adres, ostatni_adres) w prefiksie
-    fn range(&self) -> (IPv6Addr, IPv6Addr) {
-        let m = self.mask();
-        let net = self.addr & m;
-        let bcast = net | !m;
-        (net, bcast)
+/// Opis prefiksu w rekordach: postać kanoniczna, zakres, maska i rodzaj przestrzeni.
+fn prefix_value(p: &IpPrefix) -> Value {
+    Value::Obj(prefix_fields(p))
+}
+
+fn prefix_fields(p: &IpPrefix) -> Vec<(&'static str, Value)> {
+    let p = p.normalized();
+    let (family, mask, class) = match p {
+        IpPrefix::V6(v6) => {
+            let class = v6.classify().map_or("mixed", |c| c.name());
+            ("ipv6", str_value(v6.mask()), str_value(class))
+        }
+        IpPrefix::V4(v4) => ("ipv4", str_value(v4.mask()), Value::Null),
+    };
+    let mut fields = vec![
+        ("prefix", str_value(p)),
+        ("family", str_value(family)),
+        ("length", Value::Num(p.prefix_len().into())),
+    ];
+    if let Value::Obj(range) = range_value(&p) {
+        fields.extend(range);
     }
+    fields.extend([("mask", mask), ("class", class)]);
+    fields
+}
 
-    /// Czy dwa prefiksy mają wspólny fragment?
-    fn overlaps(&self, other: &Self) -> bool {
-        let (s1, e1) = self.range();
-        let (s2, e2) = other.range();
-        s1 <= e2 && s2 <= e1
+/// Rekord `info`: opis prefiksu z maską hosta, liczbą adresów i podsieci /64,
+/// pełną postacią i strefami odwrotnymi (rozdzielonymi spacją). Liczby jako
+/// tekst, bo JSON-owe liczby tracą dokładność powyżej 2^53.
+fn info_value(p: &IpPrefix) -> Value {
+    let mut fields = prefix_fields(p);
+    let extra = match p.normalized() {
+        IpPrefix::V6(v6) => [
+            ("hostmask", str_value(v6.hostmask())),
+            ("address_count", str_value(address_count(&v6))),
+            ("subnets_64", str_value(subnets_64(&v6))),
+            ("expanded", Value::Str(format!("{v6:#}"))),
+            ("reverse_zone", Value::Str(v6.to_ip6_arpa().join(" "))),
+        ],
+        IpPrefix::V4(v4) => [
+            ("hostmask", str_value(v4.hostmask())),
+            ("address_count", str_value(v4.address_count())),
+            ("subnets_64", Value::Null),
+            ("expanded", Value::Null),
+            ("reverse_zone", Value::Null),
+        ],
+    };
+    fields.extend(extra);
+    Value::Obj(fields)
+}
+
+fn address_count(p: &IPv6Prefix) -> String {
+    p.address_count().map_or_else(|| IPV6_SPACE_SIZE.to_string(), |n| n.to_string())
+}
+
+/// Liczba podsieci /64 w prefiksie; 0 dla prefiksów dłuższych niż /64.
+fn subnets_64(p: &IPv6Prefix) -> u128 {
+    match p.prefix_len() {
+        len @ 0..=64 => 1 << (64 - len),
+        _ => 0,
+    }
+}
+
+/// Rekord pary prefiksów: oba prefiksy, relacja i wspólny zakres adresów.
+fn pair_value(a: &IpPrefix, b: &IpPrefix, r: PrefixRelation) -> Value {
+    // prefiksy CIDR nakładają się tylko wtedy, gdy jeden zawiera drugi,
+    // więc wspólny zakres to zakres dłuższego z nich
+    // pola bez wartości zamiast `null`, żeby CSV zawsze miało te same kolumny
+    let overlap = if !r.overlaps() {
+        Value::Obj(vec![("first", Value::Null), ("last", Value::Null)])
+    } else if a.prefix_len() >= b.prefix_len() {
+        range_value(&a.normalized())
+    } else {
+        range_value(&b.normalized())
+    };
+    Value::Obj(vec![
+        ("a", prefix_value(a)),
+        ("b", prefix_value(b)),
+        ("relation", str_value(r.name())),
+        ("overlaps", Value::Bool(r.overlaps())),
+        ("overlap", overlap),
+    ])
+}
+
+/// Lista prefiksów jako tekst (prefiks w wierszu) albo rekordy.
+fn write_prefixes(out: &mut dyn Write, format: Format, prefixes: &[IpPrefix]) -> io::Result<()> {
+    if format == Format::Text {
+        for p in prefixes {
+            writeln!(out, "{p}")?;
+        }
+        return Ok(());
+    }
+    let records: Vec<_> = prefixes.iter().map(prefix_value).collect();
+    write_records(out, format, &records)
+}
+
+/// Prefiks z dopisanym rodzajem przestrzeni (tylko IPv6).
+fn annotated(p: &IpPrefix, lang: Lang) -> String {
+    match p {
+        IpPrefix::V6(v6) => match v6.classify() {
+            Some(class) => format!("{p} [{}]", class.in_lang(lang)),
+            None => format!("{p} [{}]", Msg::Mixed.in_lang(lang)),
+        },
+        IpPrefix::V4(_) => p.to_string(),
+    }
+}
+
+/// Ostrzega o prefiksach sprawdzanych pod kątem nakładania, które leżą
+/// w przestrzeni specjalnego przeznaczenia albo ją obejmują: jeden wiersz
+/// na rodzaj przestrzeni (`None` to przestrzeń mieszana), z pierwszym
+/// prefiksem jako przykładem.
+fn warn_special(prefixes: &[IpPrefix], lang: Lang) {
+    let mut seen = HashSet::new();
+    // rodzajów jest kilkanaście, więc wystarczy przeszukiwanie liniowe
+    let mut groups: Vec<(Option<AddrClass>, IpPrefix, usize)> = Vec::new();
+    for p in prefixes {
+        let IpPrefix::V6(v6) = p else { continue };
+        if !seen.insert(*v6) {
+            continue;
+        }
+        let class = match v6.classify() {
+            Some(class) if !class.is_special() => continue,
+            class => class,
+        };
+        match groups.iter_mut().find(|(c, _, _)| *c == class) {
+            Some((_, _, count)) => *count += 1,
+            None => groups.push((class, *p, 1)),
+        }
+    }
+    for (class, prefix, count) in groups {
+        let msg = match (class, count) {
+            (Some(class), 1) => Msg::SpecialSpace { prefix, class },
+            (Some(class), count) => Msg::SpecialSpaceMany { count, example: prefix, class },
+            (None, 1) => Msg::MixedSpace(prefix),
+            (None, count) => Msg::MixedSpaceMany { count, example: prefix },
+        };
+        eprintln!("{}", msg.in_lang(lang));
+    }
+}
+
+/// [`EXIT_FLAG`] gdy `flag`, w przeciwnym razie sukces.
+fn exit_flag(flag: bool) -> ExitCode {
+    if flag { ExitCode::from(EXIT_FLAG) } else { ExitCode::SUCCESS }
+}
+
+/// `overlap`: dla dwóch prefiksów odpowiedź tak/nie, dla listy nakładające się pary.
+fn overlap(opts: &Opts, out: &mut dyn Write) -> CliResult {
+    let lang = opts.lang;
+    let prefixes = opts.prefixes(&opts.positional)?;
+    // liczymy prefiksy po rozpisaniu zakresów, a nie argumenty
+    if opts.file.is_none() && prefixes.len() < 2 {
+        return Err(CliError::Usage(Msg::NeedTwoPrefixes("overlap")));
+    }
+    warn_special(&prefixes, lang);
+
+    // odpowiedź tak/nie tylko dla dwóch podanych prefiksów, nie dla zakresu rozpisanego na dwa
+    if let (None, [_, _], [a, b]) = (&opts.file, opts.positional.as_slice(), prefixes.as_slice()) {
+        let r = a.relation(b).map_err(|_| CliError::Input(Msg::MixedFamilies))?;
+        if opts.format == Format::Text {
+            let answer = if r.overlaps() { Msg::Yes } else { Msg::No };
+            writeln!(out, "{} ({})", answer.in_lang(lang), r.in_lang(lang))?;
+        } else {
+            write_records(out, opts.format, &[pair_value(a, b, r)])?;
+        }
+        return Ok(exit_flag(r.overlaps()));
+    }
+
+    // find_overlaps łączy w pary tylko prefiksy z tej samej rodziny
+    let pairs: Vec<_> = find_overlaps(&prefixes)
+        .into_iter()
+        .filter_map(|(i, j)| {
+            let (a, b) = (prefixes[i], prefixes[j]);
+            Some((a, b, a.relation(&b).ok()?))
+        })
+        .collect();
+    if opts.format == Format::Text {
+        for (a, b, r) in &pairs {
+            let (a, b) = (annotated(a, lang), annotated(b, lang));
+            writeln!(out, "{a} {b} ({})", r.in_lang(lang))?;
+        }
+    } else {
+        let records: Vec<_> = pairs.iter().map(|(a, b, r)| pair_value(a, b, *r)).collect();
+        write_records(out, opts.format, &records)?;
+    }
+    Ok(exit_flag(!pairs.is_empty()))
+}
+
+/// `contains <prefiks>... <adres>`: wypisuje prefiksy, w których leży adres.
+fn contains(opts: &Opts, out: &mut dyn Write) -> CliResult {
+    let Some((addr_str, list)) = opts.positional.split_last() else {
+        return Err(CliError::Usage(Msg::NeedAddress));
+    };
+    if list.is_empty() && opts.file.is_none() {
+        return Err(CliError::Usage(Msg::NeedPrefix("contains")));
+    }
+    let addr = parse_addr(addr_str)?;
+    let mut matching = opts.prefixes(list)?;
+    matching.retain(|p| p.contains_addr(addr));
+    if opts.format == Format::Text {
+        for p in &matching {
+            writeln!(out, "{}", annotated(p, opts.lang))?;
+        }
+    } else {
+        let records: Vec<_> = matching
+            .iter()
+            .map(|p| Value::Obj(vec![("address", str_value(addr)), ("prefix", prefix_value(p))]))
+            .collect();
+        write_records(out, opts.format, &records)?;
+    }
+    // jak grep: 1, gdy nic nie pasuje (overlap odwrotnie: 1, gdy coś się nakłada)
+    Ok(exit_flag(matching.is_empty()))
+}
+
+/// Wiersze `info` w trybie tekstowym: etykieta i wartość.
+fn info_rows(p: &IpPrefix, lang: Lang) -> Vec<(Msg, String)> {
+    let len = p.prefix_len();
+    match p.normalized() {
+        IpPrefix::V6(v6) => {
+            let class = match v6.classify() {
+                Some(class) => class.in_lang(lang).to_string(),
+                None => Msg::Mixed.in_lang(lang).to_string(),
+            };
+            vec![
+                (Msg::Network, v6.range().0.to_string()),
+                (Msg::Last, v6.range().1.to_string()),
+                (Msg::Length, format!("/{len}")),
+                (Msg::Mask, format!("{:#}", v6.mask())),
+                (Msg::HostMask, format!("{:#}", v6.hostmask())),
+                (Msg::Addresses, format!("2^{} = {}", 128 - len, address_count(&v6))),
+                (Msg::Subnets64, subnets_64(&v6).to_string()),
+                (Msg::Canonical, v6.to_string()),
+                (Msg::Expanded, format!("{v6:#}")),
+                (Msg::ReverseZone, v6.to_ip6_arpa().join("\n")),
+                (Msg::Class, class),
+            ]
+        }
+        IpPrefix::V4(v4) => vec![
+            (Msg::Network, v4.range().0.to_string()),
+            (Msg::Last, v4.range().1.to_string()),
+            (Msg::Length, format!("/{len}")),
+            (Msg::Mask, v4.mask().to_string()),
+            (Msg::HostMask, v4.hostmask().to_string()),
+            (Msg::Addresses, format!("2^{} = {}", 32 - len, v4.address_count())),
+            (Msg::Canonical, v4.to_string()),
+        ],
+    }
+}
+
+/// `info <prefiks>...`: zakres, maski, liczba adresów, strefa odwrotna
+/// i rodzaj przestrzeni każdego prefiksu.
+fn info(opts: &Opts, out: &mut dyn Write) -> CliResult {
+    let lang = opts.lang;
+    let prefixes = opts.prefixes(&opts.positional)?;
+    if prefixes.is_empty() {
+        return Err(CliError::Usage(Msg::NeedPrefix("info")));
+    }
+    if opts.format != Format::Text {
+        let records: Vec<_> = prefixes.iter().map(info_value).collect();
+        write_records(out, opts.format, &records)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+    for (n, p) in prefixes.iter().enumerate() {
+        if n > 0 {
+            writeln!(out)?;
+        }
+        writeln!(out, "{}", p.normalized())?;
+        let rows: Vec<_> = info_rows(p, lang)
+            .into_iter()
+            .map(|(label, value)| (format!("{}:", label.in_lang(lang)), value))
+            .collect();
+        let width = rows.iter().map(|(label, _)| label.chars().count()).max().unwrap_or(0);
+        for (label, value) in rows {
+            // kolejne wartości (np. kilka stref odwrotnych) pod pierwszą
+            let mut lines = value.lines();
+            writeln!(out, "  {label:<width$} {}", lines.next().unwrap_or(""))?;
+            for line in lines {
+                writeln!(out, "  {:width$} {line}", "")?;
+            }
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// `split <prefiks> <długość> [--skip N] [--count N]`
+fn split(opts: &Opts, out: &mut dyn Write) -> CliResult {
+    let [prefix, len] = opts.positional.as_slice() else {
+        return Err(CliError::Usage(Msg::SplitArgs));
+    };
+    let prefix = parse_arg(prefix, "", opts.lang)?;
+    let new_len: usize =
+        len.parse().map_err(|_| CliError::Usage(Msg::BadLength(len.to_string())))?;
+    let (skip, count) = (opts.skip.unwrap_or(0), opts.count.unwrap_or(SPLIT_DEFAULT_COUNT));
+    let subnets = match (u8::try_from(new_len), prefix) {
+        (Ok(len), IpPrefix::V4(p)) => take_subnets(p.subnets(len), skip, count),
+        (Ok(len), IpPrefix::V6(p)) => take_subnets(p.subnets(len), skip, count),
+        // długość ponad 255 na pewno się nie mieści; komunikat pokazuje podaną wartość
+        (Err(_), _) => None,
+    };
+    let Some((subnets, truncated)) = subnets else {
+        return Err(CliError::Input(Msg::LengthOutOfRange { len: new_len, prefix }));
+    };
+    write_prefixes(out, opts.format, &subnets)?;
+    if truncated {
+        // po liście, żeby uwaga nie wyprzedziła tego, czego dotyczy
+        out.flush()?;
+        eprintln!("{}", Msg::Truncated(count).in_lang(opts.lang));
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// `count` podsieci po pominięciu `skip` i to, czy zostały jeszcze kolejne;
+/// `None` gdy iterator był pusty.
+fn take_subnets<P: Into<IpPrefix>>(
+    mut subnets: impl Iterator<Item = P>,
+    skip: usize,
+    count: usize,
+) -> Option<(Vec<IpPrefix>, bool)> {
+    if subnets.size_hint().1 == Some(0) {
+        return None;
+    }
+    // nth przeskakuje od razu, bez generowania pominiętych podsieci
+    if skip > 0 && subnets.nth(skip - 1).is_none() {
+        return Some((Vec::new(), false));
     }
+    let taken: Vec<_> = subnets.by_ref().take(count).map(Into::into).collect();
+    let truncated = count > 0 && subnets.next().is_some();
+    Some((taken, truncated))
 }
 
-fn main() {
-    let args: Vec<_> = env::args().collect();
-    if args.len() != 3 {
-        eprintln!("Użycie: {} <prefiks1> <prefiks2>", args[0]);
-        std::process::exit(1);
+fn aggregate(opts: &Opts, out: &mut dyn Write) -> CliResult {
+    let prefixes = opts.prefixes(&opts.positional)?;
+    if prefixes.is_empty() {
+        return Err(CliError::Usage(Msg::NeedPrefix("aggregate")));
     }
-    let p1: IPv6Prefix = args[1].parse().expect("Bledny pierwszy prefiks");
-    let p2: IPv6Prefix = args[2].parse().expect("Bledny drugi prefiks");
+    write_prefixes(out, opts.format, &IpPrefix::aggregate(&prefixes))?;
+    Ok(ExitCode::SUCCESS)
+}
+
+fn exclude(opts: &Opts, out: &mut dyn Write) -> CliResult {
+    let Some((pool, excluded)) = opts.positional.split_first() else {
+        return Err(CliError::Usage(Msg::NeedPool));
+    };
+    let pool = parse_arg(pool, "", opts.lang)?;
+    let rest = IpPrefix::difference_set(&[pool], &opts.prefixes(excluded)?);
+    write_prefixes(out, opts.format, &rest)?;
+    Ok(ExitCode::SUCCESS)
+}
 
-    println!("{}", if p1.overlaps(&p2) { "tak" } else { "nie" });
+fn run(prog: &str, args: &[String], lang: Lang, out: &mut dyn Write) -> CliResult {
+    let Some(first) = args.first() else {
+        return Err(CliError::Usage(Msg::NoCommand));
+    };
+    let find = |name: &str| COMMANDS.iter().find(|c| c.name == name);
+    match first.as_str() {
+        "-h" | "--help" => {
+            out.write_all(help(prog, lang).as_bytes())?;
+            return Ok(ExitCode::SUCCESS);
+        }
+        "-V" | "--version" => {
+            writeln!(out, "{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))?;
+            return Ok(ExitCode::SUCCESS);
+        }
+        "help" => {
+            let text = match args.get(1) {
+                None => help(prog, lang),
+                Some(name) => {
+                    let cmd = find(name)
+                        .ok_or_else(|| CliError::Usage(Msg::UnknownCommand(name.clone())))?;
+                    command_help(prog, cmd, lang)
+                }
+            };
+            out.write_all(text.as_bytes())?;
+            return Ok(ExitCode::SUCCESS);
+        }
+        _ => {}
+    }
+
+    // bez nazwy polecenia argumenty to prefiksy dla `overlap`, jak w dawnym wywołaniu
+    let (cmd, rest) = match find(first) {
+        Some(cmd) => (cmd, &args[1..]),
+        None if first.bytes().all(|b| b.is_ascii_alphabetic()) => {
+            return Err(CliError::Usage(Msg::UnknownCommand(first.clone())));
+        }
+        None => (&COMMANDS[0], args),
+    };
+    if rest.iter().any(|a| a == "-h" || a == "--help") {
+        out.write_all(command_help(prog, cmd, lang).as_bytes())?;
+        return Ok(ExitCode::SUCCESS);
+    }
+    let opts = Opts::parse(cmd, rest, lang)?;
+    match cmd.name {
+        "overlap" => overlap(&opts, out),
+        "contains" => contains(&opts, out),
+        "info" => info(&opts, out),
+        "split" => split(&opts, out),
+        "aggregate" => aggregate(&opts, out),
+        "exclude" => exclude(&opts, out),
+        _ => unreachable!("polecenie z COMMANDS bez obsługi"),
+    }
+}
+
+fn main() -> ExitCode {
+    // args_os: argument spoza UTF-8 to błąd danych, a nie panika
+    let mut args: Vec<String> =
+        env::args_os().map(|a| a.to_string_lossy().into_owned()).collect();
+    let prog = args.first().cloned().unwrap_or_else(|| env!("CARGO_PKG_NAME").to_string());
+    let mut lang = env_lang();
+    let result = take_lang(&mut args, lang).and_then(|chosen| {
+        lang = chosen;
+        run(&prog, args.get(1..).unwrap_or_default(), lang, &mut io::stdout().lock())
+    });
+    let msg = match result {
+        Ok(code) => return code,
+        // odbiorca zamknął potok (np. `| head`): nie ma komu zgłaszać błędu
+        Err(CliError::Output(e)) if e.kind() == io::ErrorKind::BrokenPipe => {
+            return ExitCode::SUCCESS;
+        }
+        Err(CliError::Output(e)) => Msg::WriteFailed(e),
+        Err(CliError::Input(msg)) => msg,
+        Err(CliError::Usage(msg)) => {
+            eprintln!("{}", msg.in_lang(lang));
+            Msg::SeeHelp(prog)
+        }
+    };
+    eprintln!("{}", msg.in_lang(lang));
+    ExitCode::from(EXIT_ERROR)
 }
@@ -0,0 +1,245 @@
+//! Katalog komunikatów programu: każdy tekst widoczny dla użytkownika po
+//! angielsku i po polsku. Dane w komunikatach (prefiksy, pozycje, nazwy
+//! plików) nie zależą od języka.
+
+use std::{fmt, io};
+
+use ii_wilk_matysek::{
+    AddrClass, FamilyMismatch, HostBitsWarning, IpPrefix, Lang, Localize, PrefixParseError,
+};
+
+/// Stały tekst w obu językach (opisy poleceń, opcji i fragmenty pomocy).
+#[derive(Copy, Clone)]
+pub struct Text {
+    pub en: &'static str,
+    pub pl: &'static str,
+}
+
+impl Text {
+    pub fn get(self, lang: Lang) -> &'static str {
+        match lang {
+            Lang::En => self.en,
+            Lang::Pl => self.pl,
+        }
+    }
+}
+
+pub const HELP_INTRO: Text = Text {
+    en: "Overlap checks and other operations on IPv6 and IPv4 prefixes.",
+    pl: "Nakładanie się i operacje na prefiksach IPv6 i IPv4.",
+};
+pub const HELP_USAGE: Text = Text { en: "Usage", pl: "Użycie" };
+pub const HELP_CMD_ARGS: Text =
+    Text { en: "<command> [options] [arguments]", pl: "<polecenie> [opcje] [argumenty]" };
+pub const HELP_DEFAULT_CMD: Text = Text {
+    en: "<prefix> <prefix>...   (same as `overlap`)",
+    pl: "<prefiks> <prefiks>...   (to samo co `overlap`)",
+};
+pub const HELP_COMMANDS: Text = Text { en: "Commands", pl: "Polecenia" };
+pub const HELP_HELP_ARGS: Text = Text { en: "[<command>]", pl: "[<polecenie>]" };
+pub const HELP_OPTIONS: Text = Text { en: "Options", pl: "Opcje" };
+pub const HELP_THIS: Text = Text { en: "show this help", pl: "ta pomoc" };
+pub const HELP_VERSION: Text = Text { en: "show the program version", pl: "wersja programu" };
+pub const HELP_TAIL: Text = Text {
+    en: "Lists accept a range `first-last` in place of a prefix, and `-` reads\n\
+         the list from standard input.\n\n\
+         Exit codes: 0 – no overlap / success, 1 – prefixes overlap (overlap)\n\
+         or the address is in none of the prefixes (contains), 2 – usage or\n\
+         input error.\n",
+    pl: "W listach zamiast prefiksu można podać zakres `pierwszy-ostatni`,\n\
+         a `-` czyta listę ze standardowego wejścia.\n\n\
+         Kody wyjścia: 0 – brak nakładania / sukces, 1 – prefiksy się nakładają\n\
+         (overlap) albo adres nie leży w żadnym prefiksie (contains), 2 – błąd\n\
+         wywołania lub danych.\n",
+};
+
+/// Komunikat programu.
+pub enum Msg {
+    NoCommand,
+    UnknownCommand(String),
+    UnknownOption { flag: String, cmd: &'static str },
+    MissingValue(&'static str),
+    BadNumber { option: &'static str, value: String },
+    BadLength(String),
+    BadFormat(String),
+    BadLang(String),
+    BadPrefix { origin: String, input: String, err: PrefixParseError },
+    BadRange { origin: String, input: String, err: PrefixParseError },
+    BadAddr { input: String, err: PrefixParseError },
+    Unreadable { path: String, err: io::Error },
+    WriteFailed(io::Error),
+    MixedFamilies,
+    NeedTwoPrefixes(&'static str),
+    NeedPrefix(&'static str),
+    NeedAddress,
+    NeedPool,
+    SplitArgs,
+    LengthOutOfRange { len: usize, prefix: IpPrefix },
+    HostBits { origin: String, warning: HostBitsWarning<IpPrefix> },
+    SpecialSpace { prefix: IpPrefix, class: AddrClass },
+    MixedSpace(IpPrefix),
+    SpecialSpaceMany { count: usize, example: IpPrefix, class: AddrClass },
+    MixedSpaceMany { count: usize, example: IpPrefix },
+    Truncated(usize),
+    SeeHelp(String),
+    Yes,
+    No,
+    /// Rodzaj prefiksu obejmującego przestrzeń kilku rodzajów.
+    Mixed,
+    /// Etykiety wierszy `info`.
+    Network,
+    Last,
+    Length,
+    Mask,
+    HostMask,
+    Addresses,
+    Subnets64,
+    Canonical,
+    Expanded,
+    ReverseZone,
+    Class,
+}
+
+impl Localize for Msg {
+    fn fmt_in(&self, lang: Lang, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match lang {
+            Lang::En => self.fmt_en(f),
+            Lang::Pl => self.fmt_pl(f),
+        }
+    }
+}
+
+impl Msg {
+    fn fmt_en(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use Msg::*;
+        let en = Lang::En;
+        match self {
+            NoCommand => write!(f, "No command given"),
+            UnknownCommand(name) => write!(f, "Unknown command ‘{name}’"),
+            UnknownOption { flag, cmd } => write!(f, "Unknown option ‘{flag}’ for {cmd}"),
+            MissingValue(option) => write!(f, "Option {option} requires a value"),
+            BadNumber { option, value } => write!(f, "Invalid value for {option}: ‘{value}’"),
+            BadLength(value) => write!(f, "Invalid prefix length: ‘{value}’"),
+            BadFormat(value) => write!(f, "Unknown output format ‘{value}’ (text, json, csv)"),
+            BadLang(value) => write!(f, "Unsupported language ‘{value}’ (en, pl)"),
+            BadPrefix { origin, input, err } => {
+                write!(f, "{origin}Invalid prefix ‘{input}’: {}", err.in_lang(en))
+            }
+            BadRange { origin, input, err } => {
+                write!(f, "{origin}Invalid range ‘{input}’: {}", err.in_lang(en))
+            }
+            BadAddr { input, err } => write!(f, "Invalid address ‘{input}’: {}", err.in_lang(en)),
+            Unreadable { path, err } => write!(f, "Cannot read ‘{path}’: {err}"),
+            WriteFailed(err) => write!(f, "Write error: {err}"),
+            MixedFamilies => FamilyMismatch.fmt_in(en, f),
+            NeedTwoPrefixes(cmd) => write!(f, "{cmd} needs at least two prefixes"),
+            NeedPrefix(cmd) => write!(f, "{cmd} needs at least one prefix"),
+            NeedAddress => write!(f, "contains needs an address"),
+            NeedPool => write!(f, "exclude needs a pool prefix"),
+            SplitArgs => write!(f, "split needs a prefix and a length"),
+            LengthOutOfRange { len, prefix } => write!(f, "Length {len} does not fit in {prefix}"),
+            HostBits { origin, warning } => write!(f, "{origin}Warning: {}", warning.in_lang(en)),
+            SpecialSpace { prefix, class } => {
+                write!(f, "Warning: {prefix} is special-purpose space ({})", class.in_lang(en))
+            }
+            MixedSpace(prefix) => write!(f, "Warning: {prefix} spans special-purpose space"),
+            SpecialSpaceMany { count, example, class } => write!(
+                f,
+                "Warning: {count} prefixes are special-purpose space ({}), e.g. {example}",
+                class.in_lang(en)
+            ),
+            MixedSpaceMany { count, example } => {
+                write!(f, "Warning: {count} prefixes span special-purpose space, e.g. {example}")
+            }
+            Truncated(count) => {
+                write!(f, "… printed {count} subnets, use --skip/--count to see more")
+            }
+            SeeHelp(prog) => write!(f, "See: {prog} --help"),
+            Yes => write!(f, "yes"),
+            No => write!(f, "no"),
+            Mixed => write!(f, "mixed space"),
+            Network => write!(f, "network"),
+            Last => write!(f, "last"),
+            Length => write!(f, "prefix length"),
+            Mask => write!(f, "mask"),
+            HostMask => write!(f, "host mask"),
+            Addresses => write!(f, "addresses"),
+            Subnets64 => write!(f, "/64 subnets"),
+            Canonical => write!(f, "canonical"),
+            Expanded => write!(f, "expanded"),
+            ReverseZone => write!(f, "reverse zone"),
+            Class => write!(f, "class"),
+        }
+    }
+
+    fn fmt_pl(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use Msg::*;
+        let pl = Lang::Pl;
+        match self {
+            NoCommand => write!(f, "Brak polecenia"),
+            UnknownCommand(name) => write!(f, "Nieznane polecenie ‘{name}’"),
+            UnknownOption { flag, cmd } => write!(f, "Nieznana opcja ‘{flag}’ polecenia {cmd}"),
+            MissingValue(option) => write!(f, "Opcja {option} wymaga wartości"),
+            BadNumber { option, value } => write!(f, "Niepoprawna wartość {option}: ‘{value}’"),
+            BadLength(value) => write!(f, "Niepoprawna wartość długości: ‘{value}’"),
+            BadFormat(value) => write!(f, "Nieznany format wyjścia ‘{value}’ (text, json, csv)"),
+            BadLang(value) => write!(f, "Nieobsługiwany język ‘{value}’ (en, pl)"),
+            BadPrefix { origin, input, err } => {
+                write!(f, "{origin}Błędny prefiks ‘{input}’: {}", err.in_lang(pl))
+            }
+            BadRange { origin, input, err } => {
+                write!(f, "{origin}Błędny zakres ‘{input}’: {}", err.in_lang(pl))
+            }
+            BadAddr { input, err } => write!(f, "Błędny adres ‘{input}’: {}", err.in_lang(pl)),
+            Unreadable { path, err } => write!(f, "Nie można odczytać ‘{path}’: {err}"),
+            WriteFailed(err) => write!(f, "Błąd zapisu: {err}"),
+            MixedFamilies => FamilyMismatch.fmt_in(pl, f),
+            NeedTwoPrefixes(cmd) => write!(f, "{cmd} wymaga co najmniej dwóch prefiksów"),
+            NeedPrefix(cmd) => write!(f, "{cmd} wymaga co najmniej jednego prefiksu"),
+            NeedAddress => write!(f, "contains wymaga adresu"),
+            NeedPool => write!(f, "exclude wymaga puli"),
+            SplitArgs => write!(f, "split wymaga prefiksu i długości"),
+            LengthOutOfRange { len, prefix } => {
+                write!(f, "Długość {len} nie mieści się w {prefix}")
+            }
+            HostBits { origin, warning } => write!(f, "{origin}Uwaga: {}", warning.in_lang(pl)),
+            SpecialSpace { prefix, class } => write!(
+                f,
+                "Uwaga: {prefix} należy do przestrzeni specjalnego przeznaczenia ({})",
+                class.in_lang(pl)
+            ),
+            MixedSpace(prefix) => {
+                write!(f, "Uwaga: {prefix} obejmuje przestrzeń specjalnego przeznaczenia")
+            }
+            SpecialSpaceMany { count, example, class } => write!(
+                f,
+                "Uwaga: prefiksy w przestrzeni specjalnego przeznaczenia ({}): {count}, \
+                 np. {example}",
+                class.in_lang(pl)
+            ),
+            MixedSpaceMany { count, example } => write!(
+                f,
+                "Uwaga: prefiksy obejmujące przestrzeń specjalnego przeznaczenia: {count}, \
+                 np. {example}"
+            ),
+            Truncated(count) => {
+                write!(f, "… wypisano {count} podsieci, użyj --skip/--count, by zobaczyć więcej")
+            }
+            SeeHelp(prog) => write!(f, "Szczegóły: {prog} --help"),
+            Yes => write!(f, "tak"),
+            No => write!(f, "nie"),
+            Mixed => write!(f, "przestrzeń mieszana"),
+            Network => write!(f, "sieć"),
+            Last => write!(f, "ostatni"),
+            Length => write!(f, "długość prefiksu"),
+            Mask => write!(f, "maska"),
+            HostMask => write!(f, "maska hosta"),
+            Addresses => write!(f, "liczba adresów"),
+            Subnets64 => write!(f, "podsieci /64"),
+            Canonical => write!(f, "postać kanoniczna"),
+            Expanded => write!(f, "postać pełna"),
+            ReverseZone => write!(f, "strefa odwrotna"),
+            Class => write!(f, "rodzaj"),
+        }
+    }
+}
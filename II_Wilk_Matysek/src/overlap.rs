@@ -0,0 +1,43 @@
+use crate::IpPrefix;
+
+/// Wszystkie pary indeksów `(i, j)`, `i < j`, prefiksów mających wspólne adresy.
+///
+/// Prefiksy sortowane są po pierwszym adresie, a zamiatanie trzyma stos
+/// prefiksów wciąż „otwartych” — prefiksy CIDR zagnieżdżają się, więc każdy
+/// element stosu zawiera bieżący. Koszt to O(n log n + k) dla k par.
+/// Prefiksy z różnych rodzin nigdy się nie nakładają.
+pub fn find_overlaps<P: Copy + Into<IpPrefix>>(prefixes: &[P]) -> Vec<(usize, usize)> {
+    let mut v4 = Vec::new();
+    let mut v6 = Vec::new();
+    for (i, p) in prefixes.iter().enumerate() {
+        match (*p).into() {
+            IpPrefix::V4(p) => v4.push((p.range(), i)),
+            IpPrefix::V6(p) => v6.push((p.range(), i)),
+        }
+    }
+    let mut pairs = overlapping_pairs(v4);
+    pairs.extend(overlapping_pairs(v6));
+    pairs.sort_unstable();
+    pairs
+}
+
+/// Zamiatanie po zakresach `((pierwszy, ostatni), indeks)`.
+fn overlapping_pairs<A: Ord + Copy>(mut ranges: Vec<((A, A), usize)>) -> Vec<(usize, usize)> {
+    // przy równym początku większy prefiks (dalszy koniec) idzie pierwszy
+    ranges.sort_unstable_by(|((s1, e1), i1), ((s2, e2), i2)| {
+        s1.cmp(s2).then(e2.cmp(e1)).then(i1.cmp(i2))
+    });
+
+    let mut pairs = Vec::new();
+    let mut open: Vec<(A, usize)> = Vec::new();
+    for ((start, end), i) in ranges {
+        while open.last().is_some_and(|&(e, _)| e < start) {
+            open.pop();
+        }
+        for &(_, j) in &open {
+            pairs.push((i.min(j), i.max(j)));
+        }
+        open.push((end, i));
+    }
+    pairs
+}
@@ -0,0 +1,217 @@
+use std::{fmt, str::FromStr};
+
+use crate::{
+    IPv4Prefix, IPv6Addr, Lang, Localize, PrefixParseError, PrefixRelation, error::parse_len,
+};
+
+/// Prefiks IPv6 w postaci adres + długość maski.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct IPv6Prefix {
+    addr: IPv6Addr,
+    len: u8,
+}
+
+/// Co zrobić z bitami adresu ustawionymi za maską, np. w `2001:db8::1/32`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum HostBitsPolicy {
+    /// Błąd [`PrefixParseError::HostBitsSet`].
+    Strict,
+    /// Zerowanie bitów hosta: wynikiem jest adres sieci.
+    Normalize,
+    /// Adres bez zmian, ale z ostrzeżeniem.
+    Warn,
+}
+
+/// Prefiks miał ustawione bity hosta; `P` to typ prefiksu ([`IPv6Prefix`],
+/// [`IPv4Prefix`] albo [`IpPrefix`](crate::IpPrefix)).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct HostBitsWarning<P = IPv6Prefix> {
+    /// Prefiks w postaci podanej na wejściu.
+    pub given: P,
+    /// Ten sam prefiks z wyzerowanymi bitami hosta.
+    pub network: P,
+}
+
+impl<P: fmt::Display> fmt::Display for HostBitsWarning<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_in(Lang::En, f)
+    }
+}
+
+impl<P: fmt::Display> Localize for HostBitsWarning<P> {
+    fn fmt_in(&self, lang: Lang, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (given, network) = (&self.given, &self.network);
+        match lang {
+            Lang::En => write!(f, "{given} has host bits set, the network address is {network}"),
+            Lang::Pl => write!(f, "{given} ma ustawione bity hosta, adres sieci to {network}"),
+        }
+    }
+}
+
+/// `adres/długość`; `{:#}` rozwija adres do pełnej postaci.
+impl fmt::Display for IPv6Prefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = if f.alternate() {
+            format!("{:#}/{}", self.addr, self.len)
+        } else {
+            format!("{}/{}", self.addr, self.len)
+        };
+        f.pad(&s)
+    }
+}
+
+impl FromStr for IPv6Prefix {
+    type Err = PrefixParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (ip_str, len_str) = s.split_once('/').ok_or(PrefixParseError::MissingSlash)?;
+        let len = parse_len(len_str, ip_str.len() + 1, 128)?;
+        Ok(IPv6Prefix { addr: IPv6Addr::parse(ip_str)?, len })
+    }
+}
+
+impl IPv6Prefix {
+    /// Tworzy prefiks; `None` gdy długość maski > 128.
+    pub fn new(addr: IPv6Addr, len: u8) -> Option<Self> {
+        (len <= 128).then_some(Self { addr, len })
+    }
+
+    /// Parsuje prefiks, stosując `policy` do bitów hosta.
+    ///
+    /// Ostrzeżenie zwracane jest, gdy bity hosta były ustawione, a `policy` ich nie odrzuca.
+    pub fn parse_with(
+        s: &str,
+        policy: HostBitsPolicy,
+    ) -> Result<(Self, Option<HostBitsWarning>), PrefixParseError> {
+        let given: Self = s.parse()?;
+        if given.is_canonical() {
+            return Ok((given, None));
+        }
+        let warning = HostBitsWarning { given, network: given.normalized() };
+        match policy {
+            HostBitsPolicy::Strict => Err(PrefixParseError::HostBitsSet),
+            HostBitsPolicy::Normalize => Ok((warning.network, Some(warning))),
+            HostBitsPolicy::Warn => Ok((given, Some(warning))),
+        }
+    }
+
+    /// Adres podany przy tworzeniu prefiksu (bez maskowania).
+    pub fn addr(&self) -> IPv6Addr {
+        self.addr
+    }
+
+    /// Adres sieci, czyli `addr` z wyzerowanymi bitami hosta.
+    pub fn network(&self) -> IPv6Addr {
+        self.addr & self.mask()
+    }
+
+    /// Czy adres nie ma ustawionych bitów za maską?
+    pub fn is_canonical(&self) -> bool {
+        self.addr == self.network()
+    }
+
+    /// Ten sam prefiks z adresem sieci zamiast `addr`.
+    pub fn normalized(&self) -> Self {
+        Self { addr: self.network(), len: self.len }
+    }
+
+    /// Długość maski w bitach (0..=128).
+    pub fn prefix_len(&self) -> u8 {
+        self.len
+    }
+
+    /// Maska bitowa jako IPv6Addr
+    pub fn mask(&self) -> IPv6Addr {
+        let m = self.len;
+        // unikamy paniki
+        if m == 0 {
+            return IPv6Addr { high: 0, low: 0 };
+        }
+        let high = if m >= 64 { u64::MAX } else { (!0u64) << (64 - m) };
+        let low  = if m <= 64 { 0 } else { (!0u64) << (128 - m) };
+        IPv6Addr { high, low }
+    }
+
+    /// Zwraca (pierwszy_adres, ostatni_adres) w prefiksie
+    pub fn range(&self) -> (IPv6Addr, IPv6Addr) {
+        let m = self.mask();
+        let net = self.addr & m;
+        let bcast = net | !m;
+        (net, bcast)
+    }
+
+    /// Maska hosta (odwrotność [`mask`](Self::mask)): jedynki na bitach hosta.
+    pub fn hostmask(&self) -> IPv6Addr {
+        !self.mask()
+    }
+
+    /// Liczba adresów, 2^(128 − długość); `None` tylko dla `::/0` (2^128 nie mieści się w `u128`).
+    pub fn address_count(&self) -> Option<u128> {
+        1u128.checked_shl(128 - self.len as u32)
+    }
+
+    /// Prefiks IPv4, jeśli `self` leży w `::ffff:0:0/96` (odwrotność
+    /// [`IPv4Prefix::to_ipv6_mapped`]).
+    pub fn to_ipv4_mapped(&self) -> Option<IPv4Prefix> {
+        let v4 = self.addr.to_ipv4_mapped()?;
+        IPv4Prefix::new(v4, self.len.checked_sub(96)?)
+    }
+
+    /// Czy adres leży w prefiksie?
+    pub fn contains_addr(&self, addr: IPv6Addr) -> bool {
+        addr & self.mask() == self.network()
+    }
+
+    /// Czy dwa prefiksy mają wspólny fragment?
+    pub fn overlaps(&self, other: &Self) -> bool {
+        let (s1, e1) = self.range();
+        let (s2, e2) = other.range();
+        s1 <= e2 && s2 <= e1
+    }
+
+    /// Jak `self` ma się do `other`: równość, zawieranie, sąsiedztwo albo rozłączność.
+    pub fn relation(&self, other: &Self) -> PrefixRelation {
+        let siblings =
+            self.len == other.len && self.len > 0 && self.supernet() == other.supernet();
+        PrefixRelation::from_ranges(self.range(), other.range(), siblings)
+    }
+
+    /// Bit odróżniający `self` od rodzeństwa (ostatni bit maski); `len` musi być > 0.
+    fn last_bit(&self) -> u128 {
+        1u128 << (128 - self.len as u32)
+    }
+
+    /// Nadprefiks o długości `len - 1`; `None` dla `/0`.
+    pub fn supernet(&self) -> Option<Self> {
+        self.supernet_of_len(self.len.checked_sub(1)?)
+    }
+
+    /// Nadprefiks o długości `len`; `None` gdy `len` jest większe od długości `self`.
+    pub fn supernet_of_len(&self, len: u8) -> Option<Self> {
+        (len <= self.len).then(|| Self { addr: self.addr, len }.normalized())
+    }
+
+    /// Druga połówka wspólnego nadprefiksu; `None` dla `/0`.
+    pub fn sibling(&self) -> Option<Self> {
+        if self.len == 0 {
+            return None;
+        }
+        let addr = IPv6Addr::from_bits(self.network().to_bits() ^ self.last_bit());
+        Some(Self { addr, len: self.len })
+    }
+
+    /// Dwie połówki prefiksu (lewa, prawa); `None` dla `/128`.
+    pub fn children(&self) -> Option<(Self, Self)> {
+        if self.len == 128 {
+            return None;
+        }
+        let left = Self { addr: self.network(), len: self.len + 1 };
+        let right_addr = IPv6Addr::from_bits(left.addr.to_bits() | left.last_bit());
+        Some((left, Self { addr: right_addr, ..left }))
+    }
+
+    /// Czy prefiks jest lewą (niższą) połówką nadprefiksu? Dla `/0` zawsze `false`.
+    pub fn is_left_child(&self) -> bool {
+        self.len > 0 && self.addr.to_bits() & self.last_bit() == 0
+    }
+}
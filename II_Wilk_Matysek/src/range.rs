@@ -0,0 +1,63 @@
+use crate::{IPv4Addr, IPv4Prefix, IPv6Addr, IPv6Prefix, IpPrefix, PrefixParseError};
+
+/// Najmniejsza lista prefiksów pokrywająca dokładnie adresy `start..=end`.
+///
+/// Pusta, gdy `start > end`. Na przykład `2001:db8::100`–`2001:db8::1ff`
+/// daje jeden prefiks `2001:db8::100/120`.
+pub fn range_to_prefixes(start: IPv6Addr, end: IPv6Addr) -> Vec<IPv6Prefix> {
+    let mut out = Vec::new();
+    let (mut cur, end) = (start.to_bits(), end.to_bits());
+    if cur > end {
+        return out;
+    }
+    loop {
+        // największy blok wyrównany do `cur`, który nie wychodzi za `end`
+        let span = end - cur;
+        let align = if cur == 0 { 128 } else { cur.trailing_zeros() };
+        let fit = if span == u128::MAX { 128 } else { 127 - (span + 1).leading_zeros() };
+        let host_bits = align.min(fit);
+        let len = (128 - host_bits) as u8;
+        out.push(IPv6Prefix::new(IPv6Addr::from_bits(cur), len).expect("len <= 128"));
+
+        let last = cur | u128::MAX.checked_shr(128 - host_bits).unwrap_or(0);
+        if last >= end {
+            return out;
+        }
+        cur = last + 1;
+    }
+}
+
+/// Najmniejsza lista prefiksów IPv4 pokrywająca dokładnie adresy `start..=end`.
+pub fn range_to_prefixes_v4(start: IPv4Addr, end: IPv4Addr) -> Vec<IPv4Prefix> {
+    let map = IPv6Addr::from_ipv4_mapped;
+    range_to_prefixes(map(start), map(end))
+        .iter()
+        .filter_map(IPv6Prefix::to_ipv4_mapped)
+        .collect()
+}
+
+impl IpPrefix {
+    /// Parsuje zakres `pierwszy-ostatni` (np. `2001:db8::100-2001:db8::1ff`)
+    /// na najmniejszą listę prefiksów.
+    pub fn parse_range(s: &str) -> Result<Vec<Self>, PrefixParseError> {
+        let Some((a, b)) = s.split_once('-') else {
+            return Err(PrefixParseError::MissingDash);
+        };
+        let end_pos = a.len() + 1;
+        if a.contains(':') {
+            let start = IPv6Addr::parse(a)?;
+            let end = IPv6Addr::parse(b).map_err(|e| e.shifted(end_pos))?;
+            if start > end {
+                return Err(PrefixParseError::ReversedRange { pos: end_pos });
+            }
+            Ok(range_to_prefixes(start, end).into_iter().map(Into::into).collect())
+        } else {
+            let start: IPv4Addr = a.parse()?;
+            let end: IPv4Addr = b.parse().map_err(|e: PrefixParseError| e.shifted(end_pos))?;
+            if start > end {
+                return Err(PrefixParseError::ReversedRange { pos: end_pos });
+            }
+            Ok(range_to_prefixes_v4(start, end).into_iter().map(Into::into).collect())
+        }
+    }
+}
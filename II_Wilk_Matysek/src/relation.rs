@@ -0,0 +1,94 @@
+use std::fmt;
+
+use crate::{Lang, Localize};
+
+/// Wzajemne położenie dwóch prefiksów.
+///
+/// Prefiksy CIDR są albo rozłączne, albo jeden zawiera drugi, więc nie ma
+/// wariantu dla częściowego nakładania się.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum PrefixRelation {
+    /// Ten sam zakres adresów.
+    Equal,
+    /// Pierwszy prefiks zawiera drugi (i jest od niego większy).
+    Contains,
+    /// Pierwszy prefiks leży wewnątrz drugiego (i jest od niego mniejszy).
+    ContainedBy,
+    /// Rodzeństwo: rozłączne połówki wspólnego nadprefiksu, można je zagregować.
+    Adjacent,
+    /// Brak wspólnych adresów i nie da się ich połączyć w jeden prefiks.
+    Disjoint,
+}
+
+impl PrefixRelation {
+    /// Wyznacza relację z zakresów `(pierwszy, ostatni)` obu prefiksów.
+    ///
+    /// `siblings` mówi, czy prefiksy są połówkami tego samego nadprefiksu.
+    pub(crate) fn from_ranges<A: Ord>(a: (A, A), b: (A, A), siblings: bool) -> Self {
+        let ((s1, e1), (s2, e2)) = (a, b);
+        if s1 == s2 && e1 == e2 {
+            PrefixRelation::Equal
+        } else if s1 <= s2 && e2 <= e1 {
+            PrefixRelation::Contains
+        } else if s2 <= s1 && e1 <= e2 {
+            PrefixRelation::ContainedBy
+        } else if siblings {
+            PrefixRelation::Adjacent
+        } else {
+            debug_assert!(e1 < s2 || e2 < s1, "prefiksy CIDR nie nakładają się częściowo");
+            PrefixRelation::Disjoint
+        }
+    }
+
+    /// Czy prefiksy mają wspólne adresy?
+    pub fn overlaps(self) -> bool {
+        matches!(
+            self,
+            PrefixRelation::Equal | PrefixRelation::Contains | PrefixRelation::ContainedBy
+        )
+    }
+
+    /// Relacja widziana od strony drugiego prefiksu.
+    pub fn reversed(self) -> Self {
+        match self {
+            PrefixRelation::Contains => PrefixRelation::ContainedBy,
+            PrefixRelation::ContainedBy => PrefixRelation::Contains,
+            r => r,
+        }
+    }
+
+    /// Stała nazwa do wyjścia maszynowego.
+    pub fn name(self) -> &'static str {
+        match self {
+            PrefixRelation::Equal => "equal",
+            PrefixRelation::Contains => "contains",
+            PrefixRelation::ContainedBy => "contained-by",
+            PrefixRelation::Adjacent => "adjacent",
+            PrefixRelation::Disjoint => "disjoint",
+        }
+    }
+}
+
+impl fmt::Display for PrefixRelation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_in(Lang::En, f)
+    }
+}
+
+impl Localize for PrefixRelation {
+    fn fmt_in(&self, lang: Lang, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use PrefixRelation::*;
+        f.write_str(match (lang, self) {
+            (Lang::En, Equal) => "equal",
+            (Lang::En, Contains) => "first contains second",
+            (Lang::En, ContainedBy) => "first is contained in second",
+            (Lang::En, Adjacent) => "adjacent, can be aggregated",
+            (Lang::En, Disjoint) => "disjoint",
+            (Lang::Pl, Equal) => "równe",
+            (Lang::Pl, Contains) => "pierwszy zawiera drugi",
+            (Lang::Pl, ContainedBy) => "pierwszy zawarty w drugim",
+            (Lang::Pl, Adjacent) => "sąsiednie, można zagregować",
+            (Lang::Pl, Disjoint) => "rozłączne",
+        })
+    }
+}
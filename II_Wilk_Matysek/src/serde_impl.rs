@@ -0,0 +1,192 @@
+//! Serde (feature `serde`).
+//!
+//! Formaty czytelne dla człowieka (JSON, YAML, TOML) dostają postać tekstową,
+//! np. `"2001:db8::/32"`, czytaną tym samym parserem co `FromStr`. Formaty
+//! binarne dostają krotkę bajtów: 16 bajtów adresu IPv6 (+1 bajt długości
+//! maski dla prefiksu), a dla IPv4 odpowiednio 4 (+1).
+
+use std::{fmt, marker::PhantomData, str::FromStr};
+
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{self, SeqAccess, Visitor},
+    ser::SerializeTuple,
+};
+
+use crate::{IPv4Addr, IPv4Prefix, IPv6Addr, IPv6Prefix, IpPrefix, PrefixParseError};
+
+/// Czyta napis i parsuje go funkcją `parse`.
+struct ParseVisitor<T> {
+    parse: fn(&str) -> Result<T, PrefixParseError>,
+    expecting: &'static str,
+}
+
+impl<T> Visitor<'_> for ParseVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.expecting)
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<T, E> {
+        (self.parse)(s).map_err(E::custom)
+    }
+}
+
+/// Czyta krotkę `N` bajtów.
+struct BytesVisitor<const N: usize>(PhantomData<[u8; N]>);
+
+impl<'de, const N: usize> Visitor<'de> for BytesVisitor<N> {
+    type Value = [u8; N];
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a tuple of {N} bytes")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<[u8; N], A::Error> {
+        let mut out = [0u8; N];
+        for (i, b) in out.iter_mut().enumerate() {
+            *b = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        Ok(out)
+    }
+}
+
+fn serialize_bytes<S: Serializer>(bytes: &[u8], s: S) -> Result<S::Ok, S::Error> {
+    let mut t = s.serialize_tuple(bytes.len())?;
+    for b in bytes {
+        t.serialize_element(b)?;
+    }
+    t.end()
+}
+
+fn deserialize_bytes<'de, D: Deserializer<'de>, const N: usize>(d: D) -> Result<[u8; N], D::Error> {
+    d.deserialize_tuple(N, BytesVisitor::<N>(PhantomData))
+}
+
+fn deserialize_str<'de, D: Deserializer<'de>, T>(
+    d: D,
+    parse: fn(&str) -> Result<T, PrefixParseError>,
+    expecting: &'static str,
+) -> Result<T, D::Error> {
+    d.deserialize_str(ParseVisitor { parse, expecting })
+}
+
+impl Serialize for IPv6Addr {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            s.collect_str(self)
+        } else {
+            serialize_bytes(&self.octets(), s)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for IPv6Addr {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        if d.is_human_readable() {
+            deserialize_str(d, IPv6Addr::parse, "an IPv6 address")
+        } else {
+            deserialize_bytes::<_, 16>(d).map(IPv6Addr::from)
+        }
+    }
+}
+
+impl Serialize for IPv6Prefix {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            return s.collect_str(self);
+        }
+        let mut bytes = [0u8; 17];
+        bytes[..16].copy_from_slice(&self.addr().octets());
+        bytes[16] = self.prefix_len();
+        serialize_bytes(&bytes, s)
+    }
+}
+
+impl<'de> Deserialize<'de> for IPv6Prefix {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        if d.is_human_readable() {
+            return deserialize_str(d, IPv6Prefix::from_str, "an IPv6 prefix");
+        }
+        let bytes = deserialize_bytes::<_, 17>(d)?;
+        let addr = IPv6Addr::from(<[u8; 16]>::try_from(&bytes[..16]).expect("16 bajtów"));
+        IPv6Prefix::new(addr, bytes[16]).ok_or_else(|| {
+            de::Error::custom(PrefixParseError::LengthTooLarge { pos: 16, max: 128 })
+        })
+    }
+}
+
+impl Serialize for IPv4Addr {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            s.collect_str(self)
+        } else {
+            serialize_bytes(&self.0.to_be_bytes(), s)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for IPv4Addr {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        if d.is_human_readable() {
+            deserialize_str(d, IPv4Addr::from_str, "an IPv4 address")
+        } else {
+            deserialize_bytes::<_, 4>(d).map(|b| IPv4Addr(u32::from_be_bytes(b)))
+        }
+    }
+}
+
+impl Serialize for IPv4Prefix {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            return s.collect_str(self);
+        }
+        let [a, b, c, d] = self.addr().0.to_be_bytes();
+        serialize_bytes(&[a, b, c, d, self.prefix_len()], s)
+    }
+}
+
+impl<'de> Deserialize<'de> for IPv4Prefix {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        if d.is_human_readable() {
+            return deserialize_str(d, IPv4Prefix::from_str, "an IPv4 prefix");
+        }
+        let [a, b, c, d, len] = deserialize_bytes::<_, 5>(d)?;
+        IPv4Prefix::new(IPv4Addr(u32::from_be_bytes([a, b, c, d])), len)
+            .ok_or_else(|| de::Error::custom(PrefixParseError::LengthTooLarge { pos: 4, max: 32 }))
+    }
+}
+
+/// Postać binarna `IpPrefix`: wariant z rodziną i bajty prefiksu.
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "IpPrefix")]
+enum IpPrefixRepr {
+    V4(IPv4Prefix),
+    V6(IPv6Prefix),
+}
+
+impl Serialize for IpPrefix {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            return s.collect_str(self);
+        }
+        match *self {
+            IpPrefix::V4(p) => IpPrefixRepr::V4(p),
+            IpPrefix::V6(p) => IpPrefixRepr::V6(p),
+        }
+        .serialize(s)
+    }
+}
+
+impl<'de> Deserialize<'de> for IpPrefix {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        if d.is_human_readable() {
+            return deserialize_str(d, IpPrefix::from_str, "an IPv4 or IPv6 prefix");
+        }
+        Ok(match IpPrefixRepr::deserialize(d)? {
+            IpPrefixRepr::V4(p) => IpPrefix::V4(p),
+            IpPrefixRepr::V6(p) => IpPrefix::V6(p),
+        })
+    }
+}
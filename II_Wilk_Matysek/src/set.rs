@@ -0,0 +1,195 @@
+use std::ops::{BitAnd, BitOr, BitXor, Not, Sub};
+
+use crate::{IPv6Addr, IPv6Prefix, range_to_prefixes};
+
+/// Dowolny podzbiór przestrzeni IPv6 jako posortowana lista rozłącznych zakresów.
+///
+/// Zakresy są domknięte, a sąsiadujące lub nakładające się są scalane, więc
+/// ten sam zbiór adresów ma zawsze tę samą reprezentację i `==` porównuje
+/// zbiory, a nie sposób ich zapisania.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct PrefixSet {
+    /// `(pierwszy, ostatni)`, rosnąco, z przerwą co najmniej jednego adresu między zakresami.
+    ranges: Vec<(u128, u128)>,
+}
+
+impl PrefixSet {
+    /// Pusty zbiór.
+    pub const fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    /// Cała przestrzeń `::/0`.
+    pub fn full() -> Self {
+        Self { ranges: vec![(0, u128::MAX)] }
+    }
+
+    /// Zbiór z jednego zakresu `start..=end` (pusty, gdy `start > end`).
+    pub fn from_range(start: IPv6Addr, end: IPv6Addr) -> Self {
+        let (s, e) = (start.to_bits(), end.to_bits());
+        Self { ranges: if s <= e { vec![(s, e)] } else { Vec::new() } }
+    }
+
+    /// Dodaje adresy z prefiksu.
+    pub fn insert(&mut self, prefix: IPv6Prefix) {
+        let (s, e) = prefix.range();
+        self.insert_range(s, e);
+    }
+
+    /// Dodaje adresy `start..=end`.
+    pub fn insert_range(&mut self, start: IPv6Addr, end: IPv6Addr) {
+        *self = &*self | &Self::from_range(start, end);
+    }
+
+    /// Czy zbiór jest pusty?
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Czy zbiór obejmuje całą przestrzeń adresów?
+    pub fn is_full(&self) -> bool {
+        self.ranges == [(0, u128::MAX)]
+    }
+
+    /// Czy adres należy do zbioru?
+    pub fn contains(&self, addr: IPv6Addr) -> bool {
+        let a = addr.to_bits();
+        // pierwszy zakres kończący się na `a` lub dalej
+        let i = self.ranges.partition_point(|&(_, e)| e < a);
+        self.ranges.get(i).is_some_and(|&(s, _)| s <= a)
+    }
+
+    /// Czy wszystkie adresy prefiksu należą do zbioru?
+    pub fn contains_prefix(&self, prefix: &IPv6Prefix) -> bool {
+        let (s, e) = prefix.range();
+        let (s, e) = (s.to_bits(), e.to_bits());
+        let i = self.ranges.partition_point(|&(_, end)| end < s);
+        self.ranges.get(i).is_some_and(|&(rs, re)| rs <= s && e <= re)
+    }
+
+    /// Liczba adresów; `None` tylko dla całej przestrzeni (2^128 nie mieści się w `u128`).
+    pub fn address_count(&self) -> Option<u128> {
+        self.ranges.iter().try_fold(0u128, |acc, &(s, e)| (e - s).checked_add(1)?.checked_add(acc))
+    }
+
+    /// Zakresy `(pierwszy, ostatni)` w kolejności adresów.
+    pub fn ranges(&self) -> impl Iterator<Item = (IPv6Addr, IPv6Addr)> + '_ {
+        self.ranges.iter().map(|&(s, e)| (IPv6Addr::from_bits(s), IPv6Addr::from_bits(e)))
+    }
+
+    /// Najmniejsza lista prefiksów pokrywająca dokładnie ten zbiór.
+    pub fn to_prefixes(&self) -> Vec<IPv6Prefix> {
+        self.ranges().flat_map(|(s, e)| range_to_prefixes(s, e)).collect()
+    }
+
+    /// Suma zbiorów.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut all: Vec<(u128, u128)> = self.ranges.iter().chain(&other.ranges).copied().collect();
+        all.sort_unstable();
+        let mut ranges: Vec<(u128, u128)> = Vec::with_capacity(all.len());
+        for (s, e) in all {
+            match ranges.last_mut() {
+                // nakłada się albo styka z poprzednim zakresem
+                Some((_, last)) if s <= last.saturating_add(1) => *last = (*last).max(e),
+                _ => ranges.push((s, e)),
+            }
+        }
+        Self { ranges }
+    }
+
+    /// Część wspólna zbiorów.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (a, b) = (&self.ranges, &other.ranges);
+        let (mut i, mut j) = (0, 0);
+        let mut ranges = Vec::new();
+        while i < a.len() && j < b.len() {
+            let s = a[i].0.max(b[j].0);
+            let e = a[i].1.min(b[j].1);
+            if s <= e {
+                ranges.push((s, e));
+            }
+            // przesuwamy ten zakres, który kończy się wcześniej
+            if a[i].1 < b[j].1 { i += 1 } else { j += 1 }
+        }
+        Self { ranges }
+    }
+
+    /// Adresy spoza zbioru.
+    pub fn complement(&self) -> Self {
+        let mut ranges = Vec::with_capacity(self.ranges.len() + 1);
+        let mut next = Some(0u128);
+        for &(s, e) in &self.ranges {
+            if let Some(n) = next.filter(|&n| n < s) {
+                ranges.push((n, s - 1));
+            }
+            next = e.checked_add(1);
+        }
+        if let Some(n) = next {
+            ranges.push((n, u128::MAX));
+        }
+        Self { ranges }
+    }
+
+    /// Adresy z `self`, których nie ma w `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        self.intersection(&other.complement())
+    }
+
+    /// Adresy należące do dokładnie jednego ze zbiorów.
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        self.difference(other).union(&other.difference(self))
+    }
+}
+
+impl From<IPv6Prefix> for PrefixSet {
+    fn from(p: IPv6Prefix) -> Self {
+        let (s, e) = p.range();
+        Self::from_range(s, e)
+    }
+}
+
+impl FromIterator<IPv6Prefix> for PrefixSet {
+    fn from_iter<I: IntoIterator<Item = IPv6Prefix>>(iter: I) -> Self {
+        let ranges = iter
+            .into_iter()
+            .map(|p| (p.range().0.to_bits(), p.range().1.to_bits()))
+            .collect();
+        // union sortuje i scala, więc normalizuje też surową listę
+        Self { ranges }.union(&Self::new())
+    }
+}
+
+impl BitOr for &PrefixSet {
+    type Output = PrefixSet;
+    fn bitor(self, other: &PrefixSet) -> PrefixSet {
+        self.union(other)
+    }
+}
+
+impl BitAnd for &PrefixSet {
+    type Output = PrefixSet;
+    fn bitand(self, other: &PrefixSet) -> PrefixSet {
+        self.intersection(other)
+    }
+}
+
+impl Sub for &PrefixSet {
+    type Output = PrefixSet;
+    fn sub(self, other: &PrefixSet) -> PrefixSet {
+        self.difference(other)
+    }
+}
+
+impl BitXor for &PrefixSet {
+    type Output = PrefixSet;
+    fn bitxor(self, other: &PrefixSet) -> PrefixSet {
+        self.symmetric_difference(other)
+    }
+}
+
+impl Not for &PrefixSet {
+    type Output = PrefixSet;
+    fn not(self) -> PrefixSet {
+        self.complement()
+    }
+}
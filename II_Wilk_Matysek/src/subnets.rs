@@ -0,0 +1,132 @@
+use std::iter::FusedIterator;
+
+use crate::{IPv4Prefix, IPv6Addr, IPv6Prefix};
+
+/// Leniwy iterator po podsieciach danej długości, zwracany przez [`IPv6Prefix::subnets`].
+///
+/// `nth` i `next_back` przeskakują od razu, bez generowania pominiętych podsieci.
+#[derive(Clone, Debug)]
+pub struct Subnets {
+    /// Pierwszy adres następnej podsieci z przodu.
+    front: u128,
+    /// Pierwszy adres następnej podsieci z tyłu.
+    back: u128,
+    len: u8,
+    done: bool,
+}
+
+impl Subnets {
+    /// Liczba bitów hosta w podsieci (log2 odstępu między podsieciami).
+    fn host_bits(&self) -> u32 {
+        128 - self.len as u32
+    }
+
+    fn prefix(&self, start: u128) -> IPv6Prefix {
+        IPv6Prefix::new(IPv6Addr::from_bits(start), self.len).expect("len <= 128")
+    }
+
+    /// Liczba pozostałych podsieci minus jeden (mieści się w u128 nawet dla `::/0` → `/128`).
+    fn remaining_minus_one(&self) -> u128 {
+        (self.back - self.front).checked_shr(self.host_bits()).unwrap_or(0)
+    }
+}
+
+impl Iterator for Subnets {
+    type Item = IPv6Prefix;
+
+    fn next(&mut self) -> Option<IPv6Prefix> {
+        self.nth(0)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<IPv6Prefix> {
+        if self.done || n as u128 > self.remaining_minus_one() {
+            self.done = true;
+            return None;
+        }
+        let start = self.front + (n as u128).checked_shl(self.host_bits()).unwrap_or(0);
+        let item = self.prefix(start);
+        if start == self.back {
+            self.done = true;
+        } else {
+            self.front = start + (1u128 << self.host_bits());
+        }
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        match usize::try_from(self.remaining_minus_one()).ok().and_then(|n| n.checked_add(1)) {
+            Some(n) => (n, Some(n)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+impl DoubleEndedIterator for Subnets {
+    fn next_back(&mut self) -> Option<IPv6Prefix> {
+        if self.done {
+            return None;
+        }
+        let item = self.prefix(self.back);
+        if self.back == self.front {
+            self.done = true;
+        } else {
+            self.back -= 1u128 << self.host_bits();
+        }
+        Some(item)
+    }
+}
+
+impl FusedIterator for Subnets {}
+
+impl IPv6Prefix {
+    /// Kolejne podsieci o długości `new_len` wewnątrz prefiksu, np. `/64` w `/56`.
+    ///
+    /// Iterator jest leniwy (`/32` ma 2^32 podsieci `/64`). Dla `new_len`
+    /// mniejszego od długości prefiksu albo większego niż 128 jest pusty.
+    pub fn subnets(&self, new_len: u8) -> Subnets {
+        let (first, last) = self.range();
+        let valid = new_len >= self.prefix_len() && new_len <= 128;
+        let len = new_len.min(128);
+        let last_start = last.to_bits() & !u128::MAX.checked_shr(len as u32).unwrap_or(0);
+        Subnets { front: first.to_bits(), back: last_start, len, done: !valid }
+    }
+}
+
+/// Iterator po podsieciach IPv4, zwracany przez [`IPv4Prefix::subnets`].
+#[derive(Clone, Debug)]
+pub struct SubnetsV4(Subnets);
+
+impl Iterator for SubnetsV4 {
+    type Item = IPv4Prefix;
+
+    fn next(&mut self) -> Option<IPv4Prefix> {
+        self.0.next().and_then(|p| p.to_ipv4_mapped())
+    }
+
+    fn nth(&mut self, n: usize) -> Option<IPv4Prefix> {
+        self.0.nth(n).and_then(|p| p.to_ipv4_mapped())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for SubnetsV4 {
+    fn next_back(&mut self) -> Option<IPv4Prefix> {
+        self.0.next_back().and_then(|p| p.to_ipv4_mapped())
+    }
+}
+
+impl FusedIterator for SubnetsV4 {}
+
+impl IPv4Prefix {
+    /// Kolejne podsieci o długości `new_len`, jak [`IPv6Prefix::subnets`].
+    pub fn subnets(&self, new_len: u8) -> SubnetsV4 {
+        // długość > 32 daje > 128 w IPv6, czyli pusty iterator
+        SubnetsV4(self.to_ipv6_mapped().subnets(new_len.saturating_add(96)))
+    }
+}
@@ -0,0 +1,252 @@
+use crate::{IPv6Addr, IPv6Prefix};
+
+/// Tablica prefiksów IPv6 z wyszukiwaniem najdłuższego dopasowania (LPM).
+///
+/// Drzewo Patricia po 128 bitach adresu: węzły powstają tylko tam, gdzie
+/// ścieżki prefiksów się rozchodzą, więc głębokość jest ograniczona przez
+/// 129, a nie przez liczbę wpisów. Klucze są przechowywane jako adresy sieci
+/// (bity hosta są zerowane przy wstawianiu i wyszukiwaniu).
+#[derive(Clone, Debug)]
+pub struct PrefixTable<V> {
+    root: Option<Box<Node<V>>>,
+    len: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Node<V> {
+    prefix: IPv6Prefix,
+    /// `None` dla węzłów pośrednich, które tylko rozdzielają gałęzie.
+    value: Option<V>,
+    children: [Option<Box<Node<V>>>; 2],
+}
+
+/// Bit adresu o numerze `i`, licząc od najstarszego (0..128).
+fn bit_at(addr: IPv6Addr, i: u8) -> usize {
+    ((addr.to_bits() >> (127 - i as u32)) & 1) as usize
+}
+
+/// Czy `outer` zawiera `inner` (lub są równe)?
+fn covers(outer: &IPv6Prefix, inner: &IPv6Prefix) -> bool {
+    outer.prefix_len() <= inner.prefix_len()
+        && inner.network() & outer.mask() == outer.network()
+}
+
+impl<V> Node<V> {
+    fn new(prefix: IPv6Prefix, value: Option<V>) -> Box<Self> {
+        Box::new(Node { prefix, value, children: [None, None] })
+    }
+
+    /// Dziecko, w którego poddrzewie leży `p`; self musi zawierać `p` i być krótszy.
+    fn child_for(&self, p: &IPv6Prefix) -> usize {
+        bit_at(p.network(), self.prefix.prefix_len())
+    }
+
+    /// Dokłada wartości z całego poddrzewa w kolejności adresów.
+    fn collect<'a>(&'a self, out: &mut Vec<(IPv6Prefix, &'a V)>) {
+        if let Some(v) = &self.value {
+            out.push((self.prefix, v));
+        }
+        for child in self.children.iter().flatten() {
+            child.collect(out);
+        }
+    }
+}
+
+impl<V> Default for PrefixTable<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> PrefixTable<V> {
+    /// Pusta tablica.
+    pub const fn new() -> Self {
+        Self { root: None, len: 0 }
+    }
+
+    /// Liczba wpisów.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Czy tablica jest pusta?
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Wstawia wpis; zwraca poprzednią wartość dla tego samego prefiksu.
+    pub fn insert(&mut self, prefix: IPv6Prefix, value: V) -> Option<V> {
+        let prefix = prefix.normalized();
+        let old = insert_at(&mut self.root, prefix, value);
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+
+    /// Usuwa wpis dokładnie dla `prefix`.
+    pub fn remove(&mut self, prefix: &IPv6Prefix) -> Option<V> {
+        let old = remove_at(&mut self.root, &prefix.normalized());
+        if old.is_some() {
+            self.len -= 1;
+        }
+        old
+    }
+
+    /// Wartość dokładnie dla `prefix`.
+    pub fn get(&self, prefix: &IPv6Prefix) -> Option<&V> {
+        let prefix = prefix.normalized();
+        let mut cur = self.root.as_deref();
+        while let Some(node) = cur {
+            if node.prefix == prefix {
+                return node.value.as_ref();
+            }
+            if node.prefix.prefix_len() >= prefix.prefix_len() || !covers(&node.prefix, &prefix) {
+                return None;
+            }
+            cur = node.children[node.child_for(&prefix)].as_deref();
+        }
+        None
+    }
+
+    /// Wartość do modyfikacji dokładnie dla `prefix`.
+    pub fn get_mut(&mut self, prefix: &IPv6Prefix) -> Option<&mut V> {
+        let prefix = prefix.normalized();
+        let mut cur = self.root.as_deref_mut();
+        while let Some(node) = cur {
+            if node.prefix == prefix {
+                return node.value.as_mut();
+            }
+            if node.prefix.prefix_len() >= prefix.prefix_len() || !covers(&node.prefix, &prefix) {
+                return None;
+            }
+            let i = node.child_for(&prefix);
+            cur = node.children[i].as_deref_mut();
+        }
+        None
+    }
+
+    /// Najdłuższy prefiks zawierający `addr` — tak jak wybór trasy w FIB.
+    pub fn longest_match(&self, addr: IPv6Addr) -> Option<(IPv6Prefix, &V)> {
+        let host = IPv6Prefix::new(addr, 128).expect("len = 128");
+        self.covering(&host).pop()
+    }
+
+    /// Wszystkie wpisy zawierające `prefix` (łącznie z nim samym), od najkrótszego.
+    pub fn covering(&self, prefix: &IPv6Prefix) -> Vec<(IPv6Prefix, &V)> {
+        let prefix = prefix.normalized();
+        let mut out = Vec::new();
+        let mut cur = self.root.as_deref();
+        while let Some(node) = cur {
+            if !covers(&node.prefix, &prefix) {
+                break;
+            }
+            if let Some(v) = &node.value {
+                out.push((node.prefix, v));
+            }
+            if node.prefix.prefix_len() == prefix.prefix_len() {
+                break;
+            }
+            cur = node.children[node.child_for(&prefix)].as_deref();
+        }
+        out
+    }
+
+    /// Wszystkie wpisy zawarte w `prefix` (łącznie z nim samym), w kolejności adresów.
+    pub fn covered(&self, prefix: &IPv6Prefix) -> Vec<(IPv6Prefix, &V)> {
+        let prefix = prefix.normalized();
+        let mut out = Vec::new();
+        let mut cur = self.root.as_deref();
+        while let Some(node) = cur {
+            if covers(&prefix, &node.prefix) {
+                node.collect(&mut out);
+                break;
+            }
+            if !covers(&node.prefix, &prefix) {
+                break;
+            }
+            cur = node.children[node.child_for(&prefix)].as_deref();
+        }
+        out
+    }
+
+    /// Wszystkie wpisy w kolejności adresów (nadprefiks przed podprefiksami).
+    pub fn iter(&self) -> impl Iterator<Item = (IPv6Prefix, &V)> {
+        let mut out = Vec::with_capacity(self.len);
+        if let Some(root) = &self.root {
+            root.collect(&mut out);
+        }
+        out.into_iter()
+    }
+}
+
+impl<V> FromIterator<(IPv6Prefix, V)> for PrefixTable<V> {
+    fn from_iter<I: IntoIterator<Item = (IPv6Prefix, V)>>(iter: I) -> Self {
+        let mut table = Self::new();
+        for (p, v) in iter {
+            table.insert(p, v);
+        }
+        table
+    }
+}
+
+fn insert_at<V>(slot: &mut Option<Box<Node<V>>>, prefix: IPv6Prefix, value: V) -> Option<V> {
+    let Some(node) = slot else {
+        *slot = Some(Node::new(prefix, Some(value)));
+        return None;
+    };
+
+    if node.prefix == prefix {
+        return node.value.replace(value);
+    }
+    if covers(&node.prefix, &prefix) {
+        let i = node.child_for(&prefix);
+        return insert_at(&mut node.children[i], prefix, value);
+    }
+
+    let old = slot.take().expect("sprawdzone wyżej");
+    let new = if covers(&prefix, &old.prefix) {
+        // nowy prefiks wchodzi nad istniejący węzeł
+        let mut new = Node::new(prefix, Some(value));
+        let i = new.child_for(&old.prefix);
+        new.children[i] = Some(old);
+        new
+    } else {
+        // rozłączne: węzeł pośredni na wspólnej części obu ścieżek
+        let diff = (prefix.network().to_bits() ^ old.prefix.network().to_bits()).leading_zeros();
+        let common_len = (diff as u8).min(prefix.prefix_len()).min(old.prefix.prefix_len());
+        let common = prefix.supernet_of_len(common_len).expect("common_len <= len");
+        let mut glue = Node::new(common, None);
+        let i = glue.child_for(&prefix);
+        glue.children[i] = Some(Node::new(prefix, Some(value)));
+        glue.children[1 - i] = Some(old);
+        glue
+    };
+    *slot = Some(new);
+    None
+}
+
+fn remove_at<V>(slot: &mut Option<Box<Node<V>>>, prefix: &IPv6Prefix) -> Option<V> {
+    let node = slot.as_mut()?;
+    let removed = if node.prefix == *prefix {
+        node.value.take()
+    } else if node.prefix.prefix_len() < prefix.prefix_len() && covers(&node.prefix, prefix) {
+        let i = node.child_for(prefix);
+        remove_at(&mut node.children[i], prefix)
+    } else {
+        None
+    };
+
+    // węzeł bez wartości jest potrzebny tylko, gdy rozdziela dwie gałęzie
+    if node.value.is_none() {
+        match node.children.iter().flatten().count() {
+            0 => *slot = None,
+            1 => {
+                let child = node.children.iter_mut().find_map(Option::take);
+                *slot = child;
+            }
+            _ => {}
+        }
+    }
+    removed
+}